@@ -0,0 +1,64 @@
+#![no_main]
+
+use coord_2d::{Coord, Size};
+use libfuzzer_sys::fuzz_target;
+
+// The checked, fallible, wrapping and saturating forms of each operation must agree with
+// each other, and none of them may panic.
+fuzz_target!(|input: (Coord, Coord, Size, Size, i32, u32)| {
+    let (a, b, size_a, size_b, n, m) = input;
+
+    assert_eq!(a.checked_add_size(size_a), a.try_add_size(size_a).ok());
+    assert_eq!(a.checked_sub_size(size_a), a.try_sub_size(size_a).ok());
+    if let Some(sum) = a.checked_add(b) {
+        assert_eq!(sum, a.wrapping_add(b));
+        assert_eq!(sum, a.saturating_add(b));
+    }
+    if let Some(product) = a.checked_mul(n) {
+        assert_eq!(product, a.wrapping_mul(n));
+        assert_eq!(product, a.saturating_mul(n));
+    }
+    let _ = a.checked_div(n);
+    let _ = a.checked_rem(n);
+    let _ = a.checked_neg();
+    let _ = a.checked_magnitude2();
+    let _ = a.checked_distance2(b);
+    let manhattan = a.x.abs_diff(b.x) as u64 + a.y.abs_diff(b.y) as u64;
+    assert_eq!(
+        a.checked_manhattan_distance(b),
+        u32::try_from(manhattan).ok()
+    );
+    let _ = a.checked_manhattan_magnitude();
+
+    assert_eq!(size_a.checked_add(size_b), size_a.try_add(size_b).ok());
+    assert_eq!(size_a.checked_sub(size_b), size_a.try_sub(size_b).ok());
+    assert_eq!(size_a.checked_mul(m), size_a.try_mul(m).ok());
+    assert_eq!(size_a.checked_div(m), size_a.try_div(m).ok());
+    // Wrapping is like `u32`, with dimensions beyond the maximum reduced to it.
+    let wrapped = |f: fn(u32, u32) -> u32, rhs: (u32, u32)| {
+        Size::new(
+            f(size_a.width(), rhs.0).min(Size::max_field()),
+            f(size_a.height(), rhs.1).min(Size::max_field()),
+        )
+    };
+    let (w, h) = (size_b.width(), size_b.height());
+    assert_eq!(
+        size_a.wrapping_add(size_b),
+        wrapped(u32::wrapping_add, (w, h))
+    );
+    assert_eq!(
+        size_a.wrapping_sub(size_b),
+        wrapped(u32::wrapping_sub, (w, h))
+    );
+    assert_eq!(size_a.wrapping_mul(m), wrapped(u32::wrapping_mul, (m, m)));
+    let _ = size_a.saturating_add(size_b);
+    let _ = size_a.saturating_mul(m);
+
+    if !size_a.is_empty() {
+        let normalized = a.normalize(size_a);
+        assert!(normalized.is_valid(size_a));
+        let constrained = a.constrain(size_a).unwrap();
+        assert!(constrained.is_valid(size_a));
+        assert_eq!(constrained.constrain(size_a), Some(constrained));
+    }
+});
@@ -0,0 +1,36 @@
+#![no_main]
+
+use coord_2d::{
+    binary::{DeltaSequenceDecoder, VARINT_MAX_LEN},
+    Coord, Size,
+};
+use libfuzzer_sys::fuzz_target;
+
+// Decoding arbitrary bytes must never panic, and anything which decodes must re-encode to
+// bytes which decode to the same value.
+fuzz_target!(|data: &[u8]| {
+    let mut buf = [0; VARINT_MAX_LEN];
+    if let Ok((coord, _)) = Coord::decode_fixed(data) {
+        let len = coord.encode_fixed(&mut buf).unwrap();
+        assert_eq!(Coord::decode_fixed(&buf[..len]), Ok((coord, len)));
+    }
+    if let Ok((size, _)) = Size::decode_fixed(data) {
+        let len = size.encode_fixed(&mut buf).unwrap();
+        assert_eq!(Size::decode_fixed(&buf[..len]), Ok((size, len)));
+    }
+    if let Ok((coord, _)) = Coord::decode_varint(data) {
+        let len = coord.encode_varint(&mut buf).unwrap();
+        assert_eq!(Coord::decode_varint(&buf[..len]), Ok((coord, len)));
+    }
+    if let Ok((size, _)) = Size::decode_varint(data) {
+        let len = size.encode_varint(&mut buf).unwrap();
+        assert_eq!(Size::decode_varint(&buf[..len]), Ok((size, len)));
+    }
+    if let Ok(decoder) = DeltaSequenceDecoder::new(data) {
+        for coord in decoder {
+            if coord.is_err() {
+                break;
+            }
+        }
+    }
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use coord_2d::{Axis, Coord, Size};
+use libfuzzer_sys::fuzz_target;
+
+// Anything which parses must print in canonical form and parse back to the same value.
+fuzz_target!(|s: &str| {
+    if let Ok(coord) = s.parse::<Coord>() {
+        assert_eq!(coord.to_string().parse::<Coord>(), Ok(coord));
+    }
+    if let Ok(size) = s.parse::<Size>() {
+        assert_eq!(size.to_string().parse::<Size>(), Ok(size));
+    }
+    if let Ok(axis) = s.parse::<Axis>() {
+        assert_eq!(axis.to_string().parse::<Axis>(), Ok(axis));
+    }
+});
@@ -0,0 +1,253 @@
+//! Implementations of the `Arbitrary` traits from `proptest`, `quickcheck` and `arbitrary`,
+//! each behind a feature named after the crate. Generated `Size`s never exceed
+//! `MAX_SIZE_FIELD`. Each crate also gets a public module of the same name containing
+//! `coord_within`, for generating a `Coord` which is valid within a given `Size`, and
+//! `size_up_to`, for generating a `Size` no larger than a given `Size`, in the style of
+//! `Coord::random_within`.
+
+#[cfg(any(feature = "proptest", test))]
+pub mod with_proptest {
+    //! Strategies for generating coordinates and sizes with `proptest`.
+
+    use crate::coord::{Axis, Coord, Size};
+    use crate::fixed_coord::FixedCoord;
+    use proptest::{
+        arbitrary::{any, Arbitrary},
+        prop_oneof,
+        strategy::{BoxedStrategy, Just, Strategy},
+    };
+
+    impl Arbitrary for Coord {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Coord>;
+        fn arbitrary_with((): ()) -> Self::Strategy {
+            any::<(i32, i32)>().prop_map(Coord::from).boxed()
+        }
+    }
+
+    impl Arbitrary for Size {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Size>;
+        fn arbitrary_with((): ()) -> Self::Strategy {
+            size_up_to(Size::max())
+        }
+    }
+
+    impl Arbitrary for Axis {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Axis>;
+        fn arbitrary_with((): ()) -> Self::Strategy {
+            prop_oneof![Just(Axis::X), Just(Axis::Y)].boxed()
+        }
+    }
+
+    impl<const FRAC_BITS: u32> Arbitrary for FixedCoord<FRAC_BITS> {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<FixedCoord<FRAC_BITS>>;
+        fn arbitrary_with((): ()) -> Self::Strategy {
+            any::<Coord>().prop_map(FixedCoord::from_raw).boxed()
+        }
+    }
+
+    /// Strategy producing coordinates which are valid within `size`.
+    /// Panics if `size` is empty.
+    pub fn coord_within(size: Size) -> BoxedStrategy<Coord> {
+        assert!(!size.is_empty(), "Size is empty: {:?}", size);
+        (0..size.width(), 0..size.height())
+            .prop_map(|(x, y)| Coord::new(x as i32, y as i32))
+            .boxed()
+    }
+
+    /// Strategy producing sizes whose dimensions are at most those of `max`.
+    pub fn size_up_to(max: Size) -> BoxedStrategy<Size> {
+        (0..=max.width(), 0..=max.height())
+            .prop_map(|(x, y)| Size::new(x, y))
+            .boxed()
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+pub mod with_quickcheck {
+    //! Generators for coordinates and sizes with `quickcheck`.
+
+    use crate::coord::{Axis, Coord, Size};
+    use crate::fixed_coord::FixedCoord;
+    use alloc::boxed::Box;
+    use quickcheck::{Arbitrary, Gen};
+
+    impl Arbitrary for Coord {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Coord::new(i32::arbitrary(g), i32::arbitrary(g))
+        }
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            Box::new((self.x, self.y).shrink().map(Coord::from))
+        }
+    }
+
+    impl Arbitrary for Size {
+        fn arbitrary(g: &mut Gen) -> Self {
+            size_up_to(Size::max(), g)
+        }
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            // Shrinking only moves dimensions towards 0 so the results are always valid.
+            Box::new(
+                (self.width(), self.height())
+                    .shrink()
+                    .map(|(x, y)| Size::new(x, y)),
+            )
+        }
+    }
+
+    impl Arbitrary for Axis {
+        fn arbitrary(g: &mut Gen) -> Self {
+            *g.choose(&[Axis::X, Axis::Y]).unwrap()
+        }
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            match self {
+                Axis::X => quickcheck::empty_shrinker(),
+                Axis::Y => quickcheck::single_shrinker(Axis::X),
+            }
+        }
+    }
+
+    impl<const FRAC_BITS: u32> Arbitrary for FixedCoord<FRAC_BITS> {
+        fn arbitrary(g: &mut Gen) -> Self {
+            FixedCoord::from_raw(Coord::arbitrary(g))
+        }
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            Box::new(self.raw().shrink().map(FixedCoord::from_raw))
+        }
+    }
+
+    /// Generates a coordinate which is valid within `size`.
+    /// Panics if `size` is empty.
+    pub fn coord_within(size: Size, g: &mut Gen) -> Coord {
+        assert!(!size.is_empty(), "Size is empty: {:?}", size);
+        let x = u32::arbitrary(g) % size.width();
+        let y = u32::arbitrary(g) % size.height();
+        Coord::new(x as i32, y as i32)
+    }
+
+    /// Generates a size whose dimensions are at most those of `max`.
+    pub fn size_up_to(max: Size, g: &mut Gen) -> Size {
+        let x = (u64::from(u32::arbitrary(g)) % (u64::from(max.width()) + 1)) as u32;
+        let y = (u64::from(u32::arbitrary(g)) % (u64::from(max.height()) + 1)) as u32;
+        Size::new(x, y)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+pub mod with_arbitrary {
+    //! Generators for coordinates and sizes with `arbitrary`.
+
+    use crate::coord::{Axis, Coord, Size};
+    use crate::fixed_coord::FixedCoord;
+    use arbitrary::{Arbitrary, Result, Unstructured};
+
+    impl<'a> Arbitrary<'a> for Coord {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            Ok(Coord::new(u.arbitrary()?, u.arbitrary()?))
+        }
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            <(i32, i32)>::size_hint(depth)
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for Size {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            size_up_to(Size::max(), u)
+        }
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            <(u32, u32)>::size_hint(depth)
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for Axis {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            Ok(if u.arbitrary()? { Axis::Y } else { Axis::X })
+        }
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            bool::size_hint(depth)
+        }
+    }
+
+    impl<'a, const FRAC_BITS: u32> Arbitrary<'a> for FixedCoord<FRAC_BITS> {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            Ok(FixedCoord::from_raw(u.arbitrary()?))
+        }
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            Coord::size_hint(depth)
+        }
+    }
+
+    /// Generates a coordinate which is valid within `size`.
+    /// Panics if `size` is empty.
+    pub fn coord_within(size: Size, u: &mut Unstructured) -> Result<Coord> {
+        assert!(!size.is_empty(), "Size is empty: {:?}", size);
+        let x = u.int_in_range(0..=size.width() - 1)?;
+        let y = u.int_in_range(0..=size.height() - 1)?;
+        Ok(Coord::new(x as i32, y as i32))
+    }
+
+    /// Generates a size whose dimensions are at most those of `max`.
+    pub fn size_up_to(max: Size, u: &mut Unstructured) -> Result<Size> {
+        let x = u.int_in_range(0..=max.width())?;
+        let y = u.int_in_range(0..=max.height())?;
+        Ok(Size::new(x, y))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::coord::Size;
+    use proptest::strategy::{Just, Strategy};
+
+    proptest::proptest! {
+        #[test]
+        fn proptest_size_is_valid(size: Size) {
+            proptest::prop_assert!(Size::try_new(size.width(), size.height()).is_ok());
+        }
+
+        #[test]
+        fn proptest_coord_within(
+            (size, coord) in super::with_proptest::size_up_to(Size::new(50, 50))
+                .prop_filter("non-empty", |s| !s.is_empty())
+                .prop_flat_map(|size| (Just(size), super::with_proptest::coord_within(size)))
+        ) {
+            proptest::prop_assert!(coord.is_valid(size));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "quickcheck")]
+    fn quickcheck_within() {
+        use super::with_quickcheck::{coord_within, size_up_to};
+        use quickcheck::{Arbitrary, Gen};
+        let mut g = Gen::new(100);
+        for _ in 0..100 {
+            let size = Size::arbitrary(&mut g);
+            assert!(Size::try_new(size.width(), size.height()).is_ok());
+            let max = Size::new(10, 3);
+            let size = size_up_to(max, &mut g);
+            assert_eq!(size.pairwise_min(max), size);
+            if !size.is_empty() {
+                assert!(coord_within(size, &mut g).is_valid(size));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn arbitrary_coord_within() {
+        use super::with_arbitrary::coord_within;
+        use arbitrary::{Arbitrary, Unstructured};
+        let data = [0xFFu8; 64];
+        let mut u = Unstructured::new(&data);
+        let size = Size::arbitrary(&mut u).unwrap();
+        assert!(Size::try_new(size.width(), size.height()).is_ok());
+        let size = Size::new(7, 5);
+        for _ in 0..4 {
+            assert!(coord_within(size, &mut u).unwrap().is_valid(size));
+        }
+    }
+}
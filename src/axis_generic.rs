@@ -0,0 +1,288 @@
+//! Building blocks for code which is written once and works along either axis, by being
+//! generic over `StaticAxis`. For example, a layout routine which stacks children
+//! horizontally or vertically:
+//!
+//! ```
+//! use coord_2d::{static_axis, Coord, Size, StaticAxis};
+//!
+//! /// Position `sizes` one after another along `A`, returning the top-left corner of each.
+//! fn stack<A: StaticAxis>(sizes: &[Size]) -> Vec<Coord> {
+//!     let mut offset = 0;
+//!     sizes
+//!         .iter()
+//!         .map(|size| {
+//!             let position = Coord::new_static_axis::<A>(offset, 0);
+//!             offset += size.along::<A>() as i32;
+//!             position
+//!         })
+//!         .collect()
+//! }
+//!
+//! let sizes = [Size::new(3, 1), Size::new(2, 4)];
+//! assert_eq!(stack::<static_axis::X>(&sizes), [Coord::new(0, 0), Coord::new(3, 0)]);
+//! assert_eq!(stack::<static_axis::Y>(&sizes), [Coord::new(0, 0), Coord::new(0, 1)]);
+//! ```
+
+use crate::coord::{Coord, Size, StaticAxis};
+use crate::span::Span;
+use core::{iter::FusedIterator, marker::PhantomData, ops::Range};
+
+impl Coord {
+    /// The component of this coordinate along `A`.
+    pub fn along<A: StaticAxis>(self) -> i32 {
+        A::coord_get(self)
+    }
+
+    /// The component of this coordinate along the axis perpendicular to `A`.
+    pub fn across<A: StaticAxis>(self) -> i32 {
+        A::Other::coord_get(self)
+    }
+}
+
+impl Size {
+    /// The dimension of this size along `A`.
+    pub fn along<A: StaticAxis>(self) -> u32 {
+        A::size_get(self)
+    }
+
+    /// The dimension of this size along the axis perpendicular to `A`.
+    pub fn across<A: StaticAxis>(self) -> u32 {
+        A::Other::size_get(self)
+    }
+
+    /// The positions of the cells of this size along `A`, starting at 0.
+    pub fn range_along<A: StaticAxis>(self) -> AxisRange<A> {
+        AxisRange::new(0, dimension_end(self.along::<A>()))
+    }
+
+    /// The cells of this size in the line parallel to `A` at position `across` along the
+    /// other axis. The line is empty if `across` is outside the size.
+    pub fn line<A: StaticAxis>(self, across: i32) -> Line<A> {
+        let range = if across >= 0 && (across as u32) < self.across::<A>() {
+            self.range_along::<A>()
+        } else {
+            AxisRange::new(0, 0)
+        };
+        range.line(across)
+    }
+
+    /// Iterates over the lines parallel to `A` which make up this size. Lines parallel to
+    /// `static_axis::X` are rows, in order from top to bottom, and lines parallel to
+    /// `static_axis::Y` are columns, in order from left to right.
+    pub fn lines<A: StaticAxis>(self) -> Lines<A> {
+        Lines {
+            across: 0..dimension_end(self.across::<A>()),
+            along: self.range_along::<A>(),
+        }
+    }
+}
+
+/// Converts a dimension of a `Size` to the exclusive end of a range of positions. A dimension
+/// of `MAX_SIZE_FIELD` doesn't fit in an `i32` so it's clamped, which excludes the final
+/// position.
+fn dimension_end(dimension: u32) -> i32 {
+    i32::try_from(dimension).unwrap_or(i32::MAX)
+}
+
+/// A `Span` of positions along the axis `A`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct AxisRange<A: StaticAxis> {
+    pub span: Span,
+    axis: PhantomData<A>,
+}
+
+impl<A: StaticAxis> AxisRange<A> {
+    pub const fn new(start: i32, end: i32) -> Self {
+        Self::from_span(Span::new(start, end))
+    }
+
+    pub const fn from_span(span: Span) -> Self {
+        Self {
+            span,
+            axis: PhantomData,
+        }
+    }
+
+    /// The number of positions in the range. Ranges whose end is before their start are
+    /// empty.
+    pub const fn len(self) -> u32 {
+        self.span.len()
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.span.is_empty()
+    }
+
+    pub const fn contains(self, position: i32) -> bool {
+        self.span.contains(position)
+    }
+
+    /// Returns true if the component of `coord` along `A` is in this range.
+    pub fn contains_coord(self, coord: Coord) -> bool {
+        self.contains(coord.along::<A>())
+    }
+
+    /// The same positions along the other axis.
+    pub const fn transpose(self) -> AxisRange<A::Other> {
+        AxisRange::from_span(self.span)
+    }
+
+    /// The coordinates in this range at position `across` along the other axis.
+    pub fn line(self, across: i32) -> Line<A> {
+        Line {
+            across,
+            along: self.span.into(),
+            axis: PhantomData,
+        }
+    }
+}
+
+impl<A: StaticAxis> IntoIterator for AxisRange<A> {
+    type Item = i32;
+    type IntoIter = Range<i32>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.span.into_iter()
+    }
+}
+
+/// Iterator over the coordinates of a line parallel to the axis `A`, in increasing order along
+/// `A`.
+#[derive(Debug, Clone)]
+pub struct Line<A: StaticAxis> {
+    across: i32,
+    along: Range<i32>,
+    axis: PhantomData<A>,
+}
+
+impl<A: StaticAxis> Line<A> {
+    /// The position of the line along the other axis.
+    pub fn across(&self) -> i32 {
+        self.across
+    }
+
+    /// The positions along `A` which are yet to be yielded.
+    pub fn range(&self) -> AxisRange<A> {
+        AxisRange::new(self.along.start, self.along.end)
+    }
+}
+
+impl<A: StaticAxis> Iterator for Line<A> {
+    type Item = Coord;
+    fn next(&mut self) -> Option<Self::Item> {
+        let along = self.along.next()?;
+        Some(A::new_coord(along, self.across))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.along.size_hint()
+    }
+}
+
+impl<A: StaticAxis> DoubleEndedIterator for Line<A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let along = self.along.next_back()?;
+        Some(A::new_coord(along, self.across))
+    }
+}
+
+impl<A: StaticAxis> ExactSizeIterator for Line<A> {}
+impl<A: StaticAxis> FusedIterator for Line<A> {}
+
+/// Iterator over the lines parallel to the axis `A` which make up a `Size`, returned by
+/// `Size::lines`.
+#[derive(Debug, Clone)]
+pub struct Lines<A: StaticAxis> {
+    across: Range<i32>,
+    along: AxisRange<A>,
+}
+
+impl<A: StaticAxis> Iterator for Lines<A> {
+    type Item = Line<A>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let across = self.across.next()?;
+        Some(self.along.line(across))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.across.size_hint()
+    }
+}
+
+impl<A: StaticAxis> DoubleEndedIterator for Lines<A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let across = self.across.next_back()?;
+        Some(self.along.line(across))
+    }
+}
+
+impl<A: StaticAxis> ExactSizeIterator for Lines<A> {}
+impl<A: StaticAxis> FusedIterator for Lines<A> {}
+
+#[cfg(test)]
+mod test {
+    use super::AxisRange;
+    use crate::coord::{static_axis, Coord, Size, StaticAxis};
+
+    #[test]
+    fn along_across() {
+        let coord = Coord::new(3, -4);
+        assert_eq!(coord.along::<static_axis::X>(), 3);
+        assert_eq!(coord.across::<static_axis::X>(), -4);
+        assert_eq!(coord.along::<static_axis::Y>(), -4);
+        assert_eq!(coord.across::<static_axis::Y>(), 3);
+        let size = Size::new(80, 24);
+        assert_eq!(size.along::<static_axis::Y>(), 24);
+        assert_eq!(size.across::<static_axis::Y>(), 80);
+    }
+
+    #[test]
+    fn lines() {
+        let size = Size::new(3, 2);
+        let rows = size
+            .lines::<static_axis::X>()
+            .map(|line| line.collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                [Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0)],
+                [Coord::new(0, 1), Coord::new(1, 1), Coord::new(2, 1)],
+            ]
+        );
+        let columns = size
+            .lines::<static_axis::Y>()
+            .map(|line| line.collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            [
+                [Coord::new(0, 0), Coord::new(0, 1)],
+                [Coord::new(1, 0), Coord::new(1, 1)],
+                [Coord::new(2, 0), Coord::new(2, 1)],
+            ]
+        );
+        assert_eq!(size.line::<static_axis::Y>(3).count(), 0);
+        assert_eq!(
+            size.line::<static_axis::Y>(2).rev().collect::<Vec<_>>(),
+            [Coord::new(2, 1), Coord::new(2, 0)]
+        );
+        assert_eq!(
+            Size::new(0, 5).lines::<static_axis::X>().flatten().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn generic() {
+        fn edge_cells<A: StaticAxis>(size: Size) -> usize {
+            let last = size.across::<A>() as i32 - 1;
+            size.line::<A>(0).count() + size.line::<A>(last).count()
+        }
+        let size = Size::new(5, 3);
+        assert_eq!(edge_cells::<static_axis::X>(size), 10);
+        assert_eq!(edge_cells::<static_axis::Y>(size), 6);
+        let range = AxisRange::<static_axis::X>::new(2, 5);
+        assert_eq!(range.len(), 3);
+        assert!(range.contains_coord(Coord::new(4, 100)));
+        assert!(!range.transpose().contains_coord(Coord::new(4, 100)));
+        assert!(AxisRange::<static_axis::Y>::new(5, 2).is_empty());
+    }
+}
@@ -0,0 +1,396 @@
+//! Compact binary encodings of `Coord` and `Size`.
+//!
+//! All encodings read from and write to caller-provided byte slices, so they work without
+//! an allocator. Only the delta sequence records a version, `FORMAT_VERSION`, and it won't
+//! change without that version changing. The fixed width and varint encodings carry no
+//! version, so they are only changed with a breaking release of this crate.
+//!
+//! # Fixed width
+//!
+//! 8 bytes: the x component followed by the y component, each as a 4 byte little-endian
+//! integer (two's complement for `Coord`, unsigned for `Size`).
+//!
+//! # Varint
+//!
+//! The x component followed by the y component, each as an unsigned LEB128 integer: 7 bits
+//! per byte, least significant group first, with the high bit of each byte set if more bytes
+//! follow. `Coord` components are zigzag encoded first (0, -1, 1, -2, ... become
+//! 0, 1, 2, 3, ...) so that values near zero are short. Each component takes between 1 and 5
+//! bytes.
+//!
+//! # Delta sequence
+//!
+//! A single byte containing `FORMAT_VERSION`, followed by the number of coordinates as a
+//! LEB128 integer, followed by each coordinate in varint form as the wrapping difference
+//! from the previous coordinate. The first coordinate is relative to `(0,0)`.
+
+use crate::coord::{Coord, Size};
+use crate::error::{DecodeError, EncodeError};
+
+/// The version of the delta sequence format written by this module.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Number of bytes in the fixed width encoding of a `Coord` or `Size`.
+pub const FIXED_LEN: usize = 8;
+
+/// Maximum number of bytes in the varint encoding of a `Coord` or `Size`.
+pub const VARINT_MAX_LEN: usize = 10;
+
+/// Maximum number of bytes needed to encode a delta sequence of `count` coordinates, or `None`
+/// if `count` coordinates can't be encoded as a delta sequence.
+pub const fn delta_sequence_max_len(count: usize) -> Option<usize> {
+    if count > u32::MAX as usize {
+        return None;
+    }
+    match count.checked_mul(VARINT_MAX_LEN) {
+        Some(len) => len.checked_add(1 + 5),
+        None => None,
+    }
+}
+
+const fn zigzag_encode(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+const fn zigzag_decode(value: u32) -> i32 {
+    ((value >> 1) as i32) ^ -((value & 1) as i32)
+}
+
+fn write_u32(mut value: u32, buf: &mut [u8]) -> Result<usize, EncodeError> {
+    let mut len = 0;
+    loop {
+        let byte = buf.get_mut(len).ok_or(EncodeError::BufferTooSmall)?;
+        len += 1;
+        if value < 0x80 {
+            *byte = value as u8;
+            return Ok(len);
+        }
+        *byte = (value as u8 & 0x7F) | 0x80;
+        value >>= 7;
+    }
+}
+
+fn read_u32(buf: &[u8]) -> Result<(u32, usize), DecodeError> {
+    let mut value = 0u32;
+    for i in 0..5 {
+        let byte = *buf.get(i).ok_or(DecodeError::UnexpectedEnd)?;
+        if i == 4 && byte > 0x0F {
+            return Err(DecodeError::VarintOverflow);
+        }
+        value |= ((byte & 0x7F) as u32) << (i * 7);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(DecodeError::VarintOverflow)
+}
+
+fn write_fixed(x: [u8; 4], y: [u8; 4], buf: &mut [u8]) -> Result<usize, EncodeError> {
+    let buf = buf
+        .get_mut(0..FIXED_LEN)
+        .ok_or(EncodeError::BufferTooSmall)?;
+    buf[0..4].copy_from_slice(&x);
+    buf[4..8].copy_from_slice(&y);
+    Ok(FIXED_LEN)
+}
+
+fn read_fixed(buf: &[u8]) -> Result<([u8; 4], [u8; 4]), DecodeError> {
+    let buf = buf.get(0..FIXED_LEN).ok_or(DecodeError::UnexpectedEnd)?;
+    let mut x = [0; 4];
+    let mut y = [0; 4];
+    x.copy_from_slice(&buf[0..4]);
+    y.copy_from_slice(&buf[4..8]);
+    Ok((x, y))
+}
+
+impl Coord {
+    pub const fn to_le_bytes(self) -> [u8; FIXED_LEN] {
+        let x = self.x.to_le_bytes();
+        let y = self.y.to_le_bytes();
+        [x[0], x[1], x[2], x[3], y[0], y[1], y[2], y[3]]
+    }
+
+    pub const fn from_le_bytes(bytes: [u8; FIXED_LEN]) -> Self {
+        Self::new(
+            i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            i32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+        )
+    }
+
+    /// Writes the fixed width encoding to the start of `buf`, returning the number of bytes
+    /// written.
+    pub fn encode_fixed(self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        write_fixed(self.x.to_le_bytes(), self.y.to_le_bytes(), buf)
+    }
+
+    /// Reads the fixed width encoding from the start of `buf`, returning the coordinate and
+    /// the number of bytes read.
+    pub fn decode_fixed(buf: &[u8]) -> Result<(Self, usize), DecodeError> {
+        let (x, y) = read_fixed(buf)?;
+        Ok((
+            Self::new(i32::from_le_bytes(x), i32::from_le_bytes(y)),
+            FIXED_LEN,
+        ))
+    }
+
+    /// Writes the varint encoding to the start of `buf`, returning the number of bytes
+    /// written.
+    pub fn encode_varint(self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        let x_len = write_u32(zigzag_encode(self.x), buf)?;
+        let y_len = write_u32(zigzag_encode(self.y), &mut buf[x_len..])?;
+        Ok(x_len + y_len)
+    }
+
+    /// Reads the varint encoding from the start of `buf`, returning the coordinate and the
+    /// number of bytes read.
+    pub fn decode_varint(buf: &[u8]) -> Result<(Self, usize), DecodeError> {
+        let (x, x_len) = read_u32(buf)?;
+        let (y, y_len) = read_u32(&buf[x_len..])?;
+        Ok((Self::new(zigzag_decode(x), zigzag_decode(y)), x_len + y_len))
+    }
+}
+
+impl Size {
+    pub const fn to_le_bytes(self) -> [u8; FIXED_LEN] {
+        let x = self.width().to_le_bytes();
+        let y = self.height().to_le_bytes();
+        [x[0], x[1], x[2], x[3], y[0], y[1], y[2], y[3]]
+    }
+
+    /// Fails if either dimension is greater than `MAX_SIZE_FIELD`.
+    pub fn try_from_le_bytes(bytes: [u8; FIXED_LEN]) -> Result<Self, DecodeError> {
+        let width = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let height = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        Ok(Self::try_new(width, height)?)
+    }
+
+    /// Writes the fixed width encoding to the start of `buf`, returning the number of bytes
+    /// written.
+    pub fn encode_fixed(self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        write_fixed(self.width().to_le_bytes(), self.height().to_le_bytes(), buf)
+    }
+
+    /// Reads the fixed width encoding from the start of `buf`, returning the size and the
+    /// number of bytes read.
+    pub fn decode_fixed(buf: &[u8]) -> Result<(Self, usize), DecodeError> {
+        let (x, y) = read_fixed(buf)?;
+        let size = Self::try_new(u32::from_le_bytes(x), u32::from_le_bytes(y))?;
+        Ok((size, FIXED_LEN))
+    }
+
+    /// Writes the varint encoding to the start of `buf`, returning the number of bytes
+    /// written.
+    pub fn encode_varint(self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        let x_len = write_u32(self.width(), buf)?;
+        let y_len = write_u32(self.height(), &mut buf[x_len..])?;
+        Ok(x_len + y_len)
+    }
+
+    /// Reads the varint encoding from the start of `buf`, returning the size and the number
+    /// of bytes read.
+    pub fn decode_varint(buf: &[u8]) -> Result<(Self, usize), DecodeError> {
+        let (x, x_len) = read_u32(buf)?;
+        let (y, y_len) = read_u32(&buf[x_len..])?;
+        Ok((Self::try_new(x, y)?, x_len + y_len))
+    }
+}
+
+/// Writes `coords` as a delta sequence to the start of `buf`, returning the number of bytes
+/// written. `delta_sequence_max_len` gives a buffer size which is always sufficient.
+pub fn encode_delta_sequence(coords: &[Coord], buf: &mut [u8]) -> Result<usize, EncodeError> {
+    let count = u32::try_from(coords.len()).map_err(|_| EncodeError::TooManyCoords)?;
+    *buf.get_mut(0).ok_or(EncodeError::BufferTooSmall)? = FORMAT_VERSION;
+    let mut len = 1;
+    len += write_u32(count, &mut buf[len..])?;
+    let mut prev = Coord::new(0, 0);
+    for &coord in coords {
+        len += coord.wrapping_sub(prev).encode_varint(&mut buf[len..])?;
+        prev = coord;
+    }
+    Ok(len)
+}
+
+/// Iterator over the coordinates of an encoded delta sequence. Yields an error and then
+/// stops if the input is malformed.
+pub struct DeltaSequenceDecoder<'a> {
+    buf: &'a [u8],
+    remaining: u32,
+    prev: Coord,
+}
+
+impl<'a> DeltaSequenceDecoder<'a> {
+    /// Reads the header of the delta sequence at the start of `buf`.
+    pub fn new(buf: &'a [u8]) -> Result<Self, DecodeError> {
+        let (&version, buf) = buf.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+        if version != FORMAT_VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        let (remaining, len) = read_u32(buf)?;
+        Ok(Self {
+            buf: &buf[len..],
+            remaining,
+            prev: Coord::new(0, 0),
+        })
+    }
+
+    /// The bytes following the coordinates which have been decoded so far.
+    pub fn remaining_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+}
+
+impl Iterator for DeltaSequenceDecoder<'_> {
+    type Item = Result<Coord, DecodeError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        match Coord::decode_varint(self.buf) {
+            Ok((delta, len)) => {
+                self.buf = &self.buf[len..];
+                self.remaining -= 1;
+                self.prev = self.prev.wrapping_add(delta);
+                Some(Ok(self.prev))
+            }
+            Err(e) => {
+                self.remaining = 0;
+                Some(Err(e))
+            }
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coord::Axis;
+    use crate::error::DimensionTooLargeForSize;
+    use crate::MAX_SIZE_FIELD;
+    use proptest::prelude::*;
+
+    #[test]
+    fn zigzag() {
+        for (value, encoded) in [(0, 0), (-1, 1), (1, 2), (-2, 3), (i32::MAX, u32::MAX - 1)] {
+            assert_eq!(zigzag_encode(value), encoded);
+            assert_eq!(zigzag_decode(encoded), value);
+        }
+        assert_eq!(zigzag_decode(u32::MAX), i32::MIN);
+    }
+
+    #[test]
+    fn fixed() {
+        let coord = Coord::new(-2, 0x01020304);
+        assert_eq!(coord.to_le_bytes(), [0xFE, 0xFF, 0xFF, 0xFF, 4, 3, 2, 1]);
+        assert_eq!(Coord::from_le_bytes(coord.to_le_bytes()), coord);
+        let mut buf = [0; FIXED_LEN];
+        assert_eq!(Size::new(80, 24).encode_fixed(&mut buf), Ok(8));
+        assert_eq!(Size::decode_fixed(&buf), Ok((Size::new(80, 24), 8)));
+        assert_eq!(
+            Size::decode_fixed(&buf[1..]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Size::try_from_le_bytes([0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF]),
+            Err(DecodeError::DimensionTooLargeForSize(
+                DimensionTooLargeForSize {
+                    axis: Axis::Y,
+                    value: u32::MAX
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn varint() {
+        let mut buf = [0; VARINT_MAX_LEN];
+        assert_eq!(Coord::new(-1, 63).encode_varint(&mut buf), Ok(2));
+        assert_eq!(&buf[..2], &[1, 126]);
+        for coord in [
+            Coord::new(0, 0),
+            Coord::new(64, -65),
+            Coord::new(i32::MIN, i32::MAX),
+        ] {
+            let len = coord.encode_varint(&mut buf).unwrap();
+            assert_eq!(Coord::decode_varint(&buf), Ok((coord, len)));
+        }
+        let len = Size::new(MAX_SIZE_FIELD, 300)
+            .encode_varint(&mut buf)
+            .unwrap();
+        assert_eq!(len, 7);
+        assert_eq!(
+            Size::decode_varint(&buf),
+            Ok((Size::new(MAX_SIZE_FIELD, 300), 7))
+        );
+        assert_eq!(
+            Coord::decode_varint(&[0xFF, 0xFF, 0xFF, 0xFF, 0x10, 0]),
+            Err(DecodeError::VarintOverflow)
+        );
+        assert_eq!(
+            Coord::new(1000, 0).encode_varint(&mut buf[..2]),
+            Err(EncodeError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn delta_sequence_max_len_limits() {
+        assert_eq!(delta_sequence_max_len(0), Some(6));
+        assert_eq!(
+            delta_sequence_max_len(u32::MAX as usize),
+            (u32::MAX as usize)
+                .checked_mul(VARINT_MAX_LEN)
+                .map(|len| len + 6)
+        );
+        assert_eq!(delta_sequence_max_len(usize::MAX), None);
+    }
+
+    #[test]
+    fn delta_sequence() {
+        let coords = [
+            Coord::new(100, 100),
+            Coord::new(101, 100),
+            Coord::new(101, 99),
+            Coord::new(i32::MIN, i32::MAX),
+        ];
+        let mut buf = [0; delta_sequence_max_len(4).unwrap()];
+        let len = encode_delta_sequence(&coords, &mut buf).unwrap();
+        assert_eq!(&buf[..8], &[FORMAT_VERSION, 4, 200, 1, 200, 1, 2, 0]);
+        let mut decoder = DeltaSequenceDecoder::new(&buf[..len]).unwrap();
+        for &coord in &coords {
+            assert_eq!(decoder.next(), Some(Ok(coord)));
+        }
+        assert_eq!(decoder.next(), None);
+        assert!(decoder.remaining_bytes().is_empty());
+        assert_eq!(
+            DeltaSequenceDecoder::new(&[2, 0]).err(),
+            Some(DecodeError::UnsupportedVersion(2))
+        );
+        let mut truncated = DeltaSequenceDecoder::new(&buf[..4]).unwrap();
+        assert_eq!(truncated.next(), Some(Err(DecodeError::UnexpectedEnd)));
+        assert_eq!(truncated.next(), None);
+    }
+
+    proptest! {
+        #[test]
+        fn encode_decode_round_trip(
+            coords in prop::collection::vec(any::<Coord>(), 0..20),
+            size in any::<Size>(),
+        ) {
+            let mut buf = [0; VARINT_MAX_LEN];
+            for &coord in &coords {
+                let len = coord.encode_varint(&mut buf).unwrap();
+                prop_assert_eq!(Coord::decode_varint(&buf[..len]), Ok((coord, len)));
+            }
+            let len = size.encode_varint(&mut buf).unwrap();
+            prop_assert_eq!(Size::decode_varint(&buf[..len]), Ok((size, len)));
+            let mut buf = vec![0; delta_sequence_max_len(coords.len()).unwrap()];
+            let len = encode_delta_sequence(&coords, &mut buf).unwrap();
+            let decoded = DeltaSequenceDecoder::new(&buf[..len])
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>();
+            prop_assert_eq!(decoded, Ok(coords));
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! Binary space partitioning of a rectangle, for procedurally generating rooms connected by
+//! corridors.
+//!
+//! A rectangle is recursively split in two along an axis until every piece fits within a
+//! maximum leaf size, without creating pieces smaller than a minimum leaf size. The leaves
+//! tile the original rectangle, and each split produces a corridor joining a leaf on one side
+//! of it to a leaf on the other, so the corridors connect every leaf.
+
+use crate::coord::{Axis, Coord, Size};
+use crate::span::Span;
+use alloc::{vec, vec::Vec};
+
+/// Limits on the dimensions of the leaves of a `Bsp`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BspConfig {
+    /// No split creates a leaf smaller than this along the split axis.
+    pub min_leaf: Size,
+    /// Leaves larger than this along an axis are split along it, unless doing so would
+    /// create a leaf smaller than `min_leaf`. A maximum of at least twice the minimum, minus
+    /// one, along each axis guarantees that every leaf fits within it.
+    pub max_leaf: Size,
+}
+
+/// How a randomly generated `Bsp` chooses which axis to split a node along when it's too
+/// large along both axes.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum SplitAxis {
+    /// Split along the other axis to the parent's split, starting with the longer axis.
+    #[default]
+    Alternate,
+    /// Split along a random axis.
+    Random,
+}
+
+/// How a node is divided into its two children.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BspSplit {
+    pub axis: Axis,
+    /// The position along `axis` of the first cell of the second child.
+    pub position: i32,
+    /// The indices of the child nodes, with the first child before `position`.
+    pub children: [usize; 2],
+}
+
+/// A rectangle in a `Bsp`, which is either a leaf or split into two children.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BspNode {
+    pub top_left: Coord,
+    pub size: Size,
+    pub split: Option<BspSplit>,
+}
+
+impl BspNode {
+    pub const fn is_leaf(&self) -> bool {
+        self.split.is_none()
+    }
+}
+
+/// A straight corridor one cell wide, running along the axis of a split from the centre of a
+/// leaf on one side to the centre of a leaf on the other.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Corridor {
+    pub top_left: Coord,
+    pub size: Size,
+    /// The indices of the leaves joined by the corridor.
+    pub leaves: [usize; 2],
+}
+
+/// Decides where nodes are split.
+trait Splitter {
+    /// The axis to split a node of `size` along, where it could be split along either.
+    fn axis(&mut self, parent: Option<Axis>, size: Size) -> Axis;
+
+    /// The length along the split axis of the first child, between `min` and `max`
+    /// inclusive.
+    fn offset(&mut self, min: u32, max: u32) -> u32;
+}
+
+/// The other axis to the parent's split, or the longer axis at the root.
+fn alternate(parent: Option<Axis>, size: Size) -> Axis {
+    match parent {
+        Some(axis) => axis.other(),
+        None if size.height() > size.width() => Axis::Y,
+        None => Axis::X,
+    }
+}
+
+/// Splits nodes in half.
+struct Midpoint;
+
+impl Splitter for Midpoint {
+    fn axis(&mut self, parent: Option<Axis>, size: Size) -> Axis {
+        alternate(parent, size)
+    }
+
+    fn offset(&mut self, min: u32, max: u32) -> u32 {
+        min + (max - min) / 2
+    }
+}
+
+#[cfg(feature = "rand")]
+struct RandomSplitter<'a, R> {
+    axis: SplitAxis,
+    rng: &'a mut R,
+}
+
+#[cfg(feature = "rand")]
+impl<R: rand::Rng> Splitter for RandomSplitter<'_, R> {
+    fn axis(&mut self, parent: Option<Axis>, size: Size) -> Axis {
+        match self.axis {
+            SplitAxis::Alternate => alternate(parent, size),
+            SplitAxis::Random => {
+                if self.rng.gen() {
+                    Axis::X
+                } else {
+                    Axis::Y
+                }
+            }
+        }
+    }
+
+    fn offset(&mut self, min: u32, max: u32) -> u32 {
+        self.rng.gen_range(min..=max)
+    }
+}
+
+/// A binary space partition of a rectangle. Node 0 is the whole rectangle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Bsp {
+    nodes: Vec<BspNode>,
+}
+
+impl Bsp {
+    /// Partitions the rectangle with top-left corner `top_left` and dimensions `size`,
+    /// splitting each node in half along alternating axes. Panics if `config.min_leaf` is
+    /// empty.
+    pub fn new(top_left: Coord, size: Size, config: BspConfig) -> Self {
+        Self::build(top_left, size, config, &mut Midpoint)
+    }
+
+    /// Partitions the rectangle with top-left corner `top_left` and dimensions `size`,
+    /// splitting each node at a random position along an axis chosen by `axis`. Panics if
+    /// `config.min_leaf` is empty.
+    #[cfg(feature = "rand")]
+    pub fn random<R: rand::Rng>(
+        top_left: Coord,
+        size: Size,
+        config: BspConfig,
+        axis: SplitAxis,
+        rng: &mut R,
+    ) -> Self {
+        Self::build(top_left, size, config, &mut RandomSplitter { axis, rng })
+    }
+
+    fn build<S: Splitter>(
+        top_left: Coord,
+        size: Size,
+        config: BspConfig,
+        splitter: &mut S,
+    ) -> Self {
+        assert!(!config.min_leaf.is_empty(), "minimum leaf size is empty");
+        let mut nodes = vec![BspNode {
+            top_left,
+            size,
+            split: None,
+        }];
+        // Nodes still to be considered for splitting, along with the axis their parent was
+        // split along.
+        let mut stack = vec![(0, None)];
+        while let Some((index, parent)) = stack.pop() {
+            let BspNode { top_left, size, .. } = nodes[index];
+            let splittable = |axis| {
+                let len = size.get(axis);
+                len > config.max_leaf.get(axis)
+                    && len as u64 >= 2 * config.min_leaf.get(axis) as u64
+            };
+            let axis = match (splittable(Axis::X), splittable(Axis::Y)) {
+                (false, false) => continue,
+                (true, false) => Axis::X,
+                (false, true) => Axis::Y,
+                (true, true) => splitter.axis(parent, size),
+            };
+            let (len, across) = (size.get(axis), size.get(axis.other()));
+            let min = config.min_leaf.get(axis);
+            let offset = splitter.offset(min, len - min);
+            let second = top_left + axis.new_coord(offset as i32, 0);
+            let children = [nodes.len(), nodes.len() + 1];
+            nodes.push(BspNode {
+                top_left,
+                size: axis.new_size(offset, across),
+                split: None,
+            });
+            nodes.push(BspNode {
+                top_left: second,
+                size: axis.new_size(len - offset, across),
+                split: None,
+            });
+            nodes[index].split = Some(BspSplit {
+                axis,
+                position: second.get(axis),
+                children,
+            });
+            stack.extend(children.map(|child| (child, Some(axis))));
+        }
+        Self { nodes }
+    }
+
+    pub fn root(&self) -> &BspNode {
+        &self.nodes[0]
+    }
+
+    /// Panics if there's no node at `index`.
+    pub fn node(&self, index: usize) -> &BspNode {
+        &self.nodes[index]
+    }
+
+    /// Every node in the tree. The children of a node always come after it.
+    pub fn nodes(&self) -> &[BspNode] {
+        &self.nodes
+    }
+
+    /// The indices of the leaves in the subtree rooted at the node at `index`.
+    pub fn leaves_of(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut stack = vec![index];
+        core::iter::from_fn(move || loop {
+            let index = stack.pop()?;
+            match self.nodes[index].split {
+                Some(split) => stack.extend(split.children.into_iter().rev()),
+                None => return Some(index),
+            }
+        })
+    }
+
+    /// The rectangles of the leaves, as their top-left corners and dimensions.
+    pub fn leaves(&self) -> impl Iterator<Item = (Coord, Size)> + '_ {
+        self.leaves_of(0).map(|index| {
+            let node = &self.nodes[index];
+            (node.top_left, node.size)
+        })
+    }
+
+    /// The rectangles of the leaves shrunk by `margin` cells on every side, leaving space
+    /// between rooms generated in adjacent leaves. Leaves too small to shrink are skipped.
+    pub fn rooms(&self, margin: u32) -> impl Iterator<Item = (Coord, Size)> + '_ {
+        self.leaves().filter_map(move |(top_left, size)| {
+            let margin_size = Size::new(margin, margin);
+            let room = size.checked_sub(margin_size)?.checked_sub(margin_size)?;
+            (!room.is_empty()).then(|| (top_left + margin_size, room))
+        })
+    }
+
+    /// One corridor for each split, joining the pair of leaves on either side of it which
+    /// share the longest stretch of its boundary. Together the corridors connect every leaf.
+    pub fn corridors(&self) -> Vec<Corridor> {
+        let mut corridors = Vec::new();
+        for node in &self.nodes {
+            let Some(BspSplit {
+                axis,
+                position,
+                children: [first, second],
+            }) = node.split
+            else {
+                continue;
+            };
+            let across = |index: usize| {
+                let leaf = &self.nodes[index];
+                Span::of_rect(leaf.top_left, leaf.size, axis.other())
+            };
+            let along = |index: usize| {
+                Span::of_rect(self.nodes[index].top_left, self.nodes[index].size, axis)
+            };
+            let mut best: Option<(u32, usize, usize)> = None;
+            for a in self.leaves_of(first).filter(|&a| along(a).end == position) {
+                for b in self
+                    .leaves_of(second)
+                    .filter(|&b| along(b).start == position)
+                {
+                    let shared = across(a).intersection(across(b)).len();
+                    if best.is_none_or(|(best, _, _)| shared > best) {
+                        best = Some((shared, a, b));
+                    }
+                }
+            }
+            let (_, a, b) = best.expect("leaves on either side of a split share a boundary");
+            let shared = across(a).intersection(across(b));
+            let centre = |span: Span| span.start + (span.len() / 2) as i32;
+            let (top_left, size) = Span::new(centre(along(a)), centre(along(b)) + 1)
+                .to_rect(Span::with_len(centre(shared), 1), axis);
+            corridors.push(Corridor {
+                top_left,
+                size,
+                leaves: [a, b],
+            });
+        }
+        corridors
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bsp, BspConfig};
+    use crate::coord::{Coord, Size};
+    use crate::coord_bit_set::CoordBitSet;
+    use crate::neighbourhood::Neighbourhood;
+    use proptest::prelude::*;
+
+    fn cells(top_left: Coord, size: Size) -> impl Iterator<Item = Coord> {
+        size.coord_iter_row_major()
+            .map(move |offset| top_left + offset)
+    }
+
+    /// Checks the leaves tile the root within the configured limits and the corridors join
+    /// every room.
+    fn check(bsp: &Bsp, config: BspConfig) {
+        let root = *bsp.root();
+        let mut covered = CoordBitSet::new(root.size);
+        for (top_left, size) in bsp.leaves() {
+            assert!(size.width() >= config.min_leaf.width().min(root.size.width()));
+            assert!(size.height() >= config.min_leaf.height().min(root.size.height()));
+            for coord in cells(top_left, size) {
+                assert!(covered.insert(coord - root.top_left));
+            }
+        }
+        assert_eq!(covered.count(), root.size.count());
+        let mut open = CoordBitSet::new(root.size);
+        for (top_left, size) in bsp.rooms(1) {
+            cells(top_left, size).for_each(|coord| {
+                open.insert(coord - root.top_left);
+            });
+        }
+        let corridors = bsp.corridors();
+        assert_eq!(corridors.len(), bsp.leaves().count() - 1);
+        for corridor in corridors {
+            cells(corridor.top_left, corridor.size).for_each(|coord| {
+                open.insert(coord - root.top_left);
+            });
+        }
+        if let Some(start) = open.iter().next() {
+            let region = root
+                .size
+                .flood_fill(start, Neighbourhood::Four, |coord| open.contains(coord))
+                .unwrap();
+            assert_eq!(region.cells, open);
+        }
+    }
+
+    #[test]
+    fn midpoint() {
+        let config = BspConfig {
+            min_leaf: Size::new(4, 4),
+            max_leaf: Size::new(10, 10),
+        };
+        let bsp = Bsp::new(Coord::new(-5, 3), Size::new(40, 20), config);
+        assert_eq!(bsp.leaves().count(), 8);
+        assert!(bsp.leaves().all(|(_, size)| size == Size::new(10, 10)));
+        let root = bsp.root().split.unwrap();
+        assert_eq!(root.axis, crate::Axis::X);
+        assert_eq!(root.position, 15);
+        check(&bsp, config);
+        let small = Bsp::new(Coord::new(0, 0), Size::new(7, 7), config);
+        assert!(small.root().is_leaf());
+        assert!(small.corridors().is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn valid_partitions(
+            (width, height) in (1..60u32, 1..60u32),
+            (min_width, min_height) in (1..6u32, 1..6u32),
+            (extra_width, extra_height) in (0..10u32, 0..10u32),
+        ) {
+            let config = BspConfig {
+                min_leaf: Size::new(min_width, min_height),
+                max_leaf: Size::new(2 * min_width - 1 + extra_width, 2 * min_height - 1 + extra_height),
+            };
+            let bsp = Bsp::new(Coord::new(3, -7), Size::new(width, height), config);
+            check(&bsp, config);
+            prop_assert!(bsp.leaves().all(|(_, size)| size.width() <= config.max_leaf.width() && size.height() <= config.max_leaf.height()));
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random() {
+        use super::SplitAxis;
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(0);
+        let config = BspConfig {
+            min_leaf: Size::new(5, 3),
+            max_leaf: Size::new(12, 8),
+        };
+        for axis in [SplitAxis::Alternate, SplitAxis::Random] {
+            for _ in 0..20 {
+                let bsp = Bsp::random(Coord::new(0, 0), Size::new(80, 50), config, axis, &mut rng);
+                check(&bsp, config);
+                assert!(bsp
+                    .leaves()
+                    .all(|(_, size)| size.width() <= 12 && size.height() <= 8));
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! `Coord` and `FixedCoord` are `Pod`, so slices of them can be cast to and from bytes
+//! freely. `Size` is only `NoUninit` and `CheckedBitPattern`, as not every bit pattern is a
+//! valid `Size`: casting bytes to `Size` must go through `bytemuck::checked`, which rejects
+//! dimensions greater than `MAX_SIZE_FIELD`.
+
+use crate::coord::{Coord, Size, MAX_SIZE_FIELD};
+use crate::fixed_coord::FixedCoord;
+use bytemuck::{CheckedBitPattern, NoUninit, Pod, Zeroable};
+
+// SAFETY: `Coord` is `#[repr(C)]` with two `i32` fields, so it has no padding and every bit
+// pattern, including all zeros, is a valid `Coord`. It is `Copy` and `'static`.
+unsafe impl Zeroable for Coord {}
+unsafe impl Pod for Coord {}
+
+// SAFETY: `FixedCoord` is `#[repr(transparent)]` over a `Coord`, which is `Pod`.
+unsafe impl<const FRAC_BITS: u32> Zeroable for FixedCoord<FRAC_BITS> {}
+unsafe impl<const FRAC_BITS: u32> Pod for FixedCoord<FRAC_BITS> {}
+
+// SAFETY: `Size` is `#[repr(C)]` with two `u32` fields, so it has no padding bytes which could
+// be uninitialised, and a size of 0x0 is valid. It is `Copy` and `'static`.
+unsafe impl Zeroable for Size {}
+unsafe impl NoUninit for Size {}
+
+// SAFETY: `[u32; 2]` has the same layout as `Size`, and `is_valid_bit_pattern` accepts exactly
+// the pairs of dimensions which `Size::try_new` accepts.
+unsafe impl CheckedBitPattern for Size {
+    type Bits = [u32; 2];
+    fn is_valid_bit_pattern(&[x, y]: &[u32; 2]) -> bool {
+        x <= MAX_SIZE_FIELD && y <= MAX_SIZE_FIELD
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::coord::{Coord, Size};
+    use bytemuck::checked::CheckedCastError;
+
+    #[test]
+    fn cast() {
+        let coords = [Coord::new(1, -1), Coord::new(2, 3)];
+        let ints: &[i32] = bytemuck::cast_slice(&coords);
+        assert_eq!(ints, &[1, -1, 2, 3]);
+        let bytes: &[u8] = bytemuck::cast_slice(&coords);
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(bytemuck::cast_slice::<i32, Coord>(ints), &coords);
+        let sizes = [Size::new(80, 24)];
+        let ints: &[u32] = bytemuck::cast_slice(&sizes);
+        assert_eq!(ints, &[80, 24]);
+        assert_eq!(bytemuck::checked::try_cast_slice(ints), Ok(&sizes[..]));
+        assert_eq!(
+            bytemuck::checked::try_cast_slice::<u32, Size>(&[0, u32::MAX]),
+            Err(CheckedCastError::InvalidBitPattern)
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Stepping a grid of cells within a `Size` through generations of a cellular automaton.
+
+use crate::coord::{Coord, Size};
+use crate::neighbourhood::Neighbourhood;
+use crate::rule::Rule;
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+use core::slice;
+
+/// How to treat the neighbours of cells on the edge of the grid which lie outside it.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Edge<T> {
+    /// Use the nearest cell inside the grid, like `Coord::constrain`, so cells on the edge
+    /// count themselves or their neighbours along the edge again.
+    Clamp,
+    /// Use the cell on the opposite side of the grid, like `Coord::normalize`, making the
+    /// grid a torus.
+    Wrap,
+    /// Treat every cell outside the grid as having this value.
+    Constant(T),
+}
+
+/// A grid of cells within a `Size` which is updated a generation at a time, with each cell's
+/// next value depending on its current value and the values of its neighbours.
+#[derive(Debug)]
+pub struct CellularAutomaton<T> {
+    size: Size,
+    /// The cells in row-major order.
+    cells: Vec<T>,
+    /// Holds the next generation while it's computed, to avoid reallocating every step.
+    next: Vec<T>,
+}
+
+impl<T> CellularAutomaton<T> {
+    /// A grid where each cell is initialised to the result of calling `f` with its coordinate.
+    pub fn new_with<F: FnMut(Coord) -> T>(size: Size, f: F) -> Self {
+        Self {
+            size,
+            cells: size.coord_iter_row_major().map(f).collect(),
+            next: Vec::new(),
+        }
+    }
+
+    /// A grid where every cell is initialised to `value`.
+    pub fn new(size: Size, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new_with(size, |_| value.clone())
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    fn index(&self, coord: Coord) -> Option<usize> {
+        coord
+            .is_valid(self.size)
+            .then(|| coord.y as usize * self.size.width() as usize + coord.x as usize)
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index(coord).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index(coord).map(|index| &mut self.cells[index])
+    }
+
+    /// The cells in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Iterates over the cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.size.coord_iter_row_major().zip(self.cells.iter())
+    }
+
+    /// Advances every cell by one generation, replacing it with the result of calling `f`
+    /// with its coordinate, its current value and its neighbours in `neighbourhood`. Every
+    /// call to `f` sees the current generation, regardless of the order cells are updated.
+    pub fn step<F: FnMut(Coord, &T, CellNeighbours<'_, T>) -> T>(
+        &mut self,
+        neighbourhood: Neighbourhood,
+        edge: &Edge<T>,
+        mut f: F,
+    ) {
+        let mut next = core::mem::take(&mut self.next);
+        next.clear();
+        for (coord, cell) in self.iter() {
+            let neighbours = CellNeighbours {
+                size: self.size,
+                cells: &self.cells,
+                coord,
+                offsets: neighbourhood.offsets().iter(),
+                edge,
+            };
+            next.push(f(coord, cell, neighbours));
+        }
+        self.next = core::mem::replace(&mut self.cells, next);
+    }
+}
+
+// The scratch buffer isn't part of the automaton's state, so it's neither compared, hashed nor
+// cloned.
+
+impl<T: Clone> Clone for CellularAutomaton<T> {
+    fn clone(&self) -> Self {
+        Self {
+            size: self.size,
+            cells: self.cells.clone(),
+            next: Vec::new(),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for CellularAutomaton<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for CellularAutomaton<T> {}
+
+impl<T: Hash> Hash for CellularAutomaton<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.size.hash(state);
+        self.cells.hash(state);
+    }
+}
+
+impl CellularAutomaton<bool> {
+    /// Advances every cell by one generation of a birth/survival `rule`, where true cells are
+    /// alive.
+    pub fn step_rule(&mut self, rule: Rule, neighbourhood: Neighbourhood, edge: &Edge<bool>) {
+        self.step(neighbourhood, edge, |_, &alive, neighbours| {
+            rule.next(alive, neighbours.filter(|&&alive| alive).count() as u8)
+        });
+    }
+}
+
+/// Iterator over the values of the neighbours of a cell, passed to the update function of
+/// `CellularAutomaton::step`. Neighbours outside the grid are resolved according to the
+/// `Edge`, so this yields one value per offset in the neighbourhood.
+#[derive(Debug, Clone)]
+pub struct CellNeighbours<'a, T> {
+    size: Size,
+    cells: &'a [T],
+    coord: Coord,
+    offsets: slice::Iter<'static, Coord>,
+    edge: &'a Edge<T>,
+}
+
+impl<'a, T> Iterator for CellNeighbours<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offsets.next()?;
+        // Computed in i64 as a cell on the edge of a very large grid may have neighbours
+        // which can't be represented by a `Coord`.
+        let (width, height) = (self.size.width() as i64, self.size.height() as i64);
+        let (mut x, mut y) = (
+            self.coord.x as i64 + offset.x as i64,
+            self.coord.y as i64 + offset.y as i64,
+        );
+        if x < 0 || x >= width || y < 0 || y >= height {
+            match self.edge {
+                Edge::Clamp => {
+                    x = x.clamp(0, width - 1);
+                    y = y.clamp(0, height - 1);
+                }
+                Edge::Wrap => {
+                    x = x.rem_euclid(width);
+                    y = y.rem_euclid(height);
+                }
+                Edge::Constant(value) => return Some(value),
+            }
+        }
+        Some(&self.cells[y as usize * width as usize + x as usize])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.offsets.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for CellNeighbours<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::{CellularAutomaton, Edge};
+    use crate::coord::{Coord, Size};
+    use crate::neighbourhood::Neighbourhood;
+    use crate::rule::Rule;
+
+    fn grid(rows: &[&str]) -> CellularAutomaton<bool> {
+        let size = Size::new(rows[0].len() as u32, rows.len() as u32);
+        CellularAutomaton::new_with(size, |coord| {
+            rows[coord.y as usize].as_bytes()[coord.x as usize] == b'#'
+        })
+    }
+
+    fn live(automaton: &CellularAutomaton<bool>) -> Vec<Coord> {
+        automaton
+            .iter()
+            .filter(|&(_, &alive)| alive)
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+
+    #[test]
+    fn blinker() {
+        let mut automaton = grid(&[".....", "..#..", "..#..", "..#..", "....."]);
+        let vertical = live(&automaton);
+        automaton.step_rule(Rule::LIFE, Neighbourhood::Eight, &Edge::Constant(false));
+        assert_eq!(
+            live(&automaton),
+            [Coord::new(1, 2), Coord::new(2, 2), Coord::new(3, 2)]
+        );
+        automaton.step_rule(Rule::LIFE, Neighbourhood::Eight, &Edge::Constant(false));
+        assert_eq!(live(&automaton), vertical);
+    }
+
+    #[test]
+    fn scratch_buffer_is_not_state() {
+        let rows = [".....", "..#..", "..#..", "..#..", "....."];
+        let mut automaton = grid(&rows);
+        automaton.step_rule(Rule::LIFE, Neighbourhood::Eight, &Edge::Constant(false));
+        automaton.step_rule(Rule::LIFE, Neighbourhood::Eight, &Edge::Constant(false));
+        assert!(!automaton.next.is_empty());
+        assert!(automaton.clone().next.is_empty());
+        let fresh = grid(&rows);
+        assert_eq!(automaton, fresh);
+        let set = std::collections::HashSet::from([automaton]);
+        assert!(set.contains(&fresh));
+    }
+
+    #[test]
+    fn glider_wraps() {
+        let mut automaton = grid(&[".#....", "..#...", "###...", "......", "......", "......"]);
+        let start = live(&automaton);
+        // A glider moves one cell diagonally every 4 generations.
+        for _ in 0..4 * 6 {
+            automaton.step_rule(Rule::LIFE, Neighbourhood::Eight, &Edge::Wrap);
+        }
+        assert_eq!(live(&automaton), start);
+    }
+
+    #[test]
+    fn edges() {
+        let automaton = CellularAutomaton::new_with(Size::new(3, 1), |coord| coord.x);
+        let mut seen = Vec::new();
+        let mut record = |edge| {
+            let mut automaton = automaton.clone();
+            automaton.step(Neighbourhood::Four, &edge, |coord, &value, neighbours| {
+                if coord.x == 0 {
+                    seen.push(neighbours.copied().collect::<Vec<_>>());
+                }
+                value
+            });
+        };
+        record(Edge::Clamp);
+        record(Edge::Wrap);
+        record(Edge::Constant(-1));
+        // Neighbours are clockwise from above: up, right, down, left.
+        assert_eq!(seen, [[0, 1, 0, 0], [0, 1, 0, 2], [-1, 1, -1, -1]]);
+    }
+
+    #[test]
+    fn von_neumann() {
+        let mut automaton = grid(&["...", ".#.", "..."]);
+        automaton.step_rule(Rule::new(&[1], &[]), Neighbourhood::Four, &Edge::Clamp);
+        assert_eq!(
+            live(&automaton),
+            [
+                Coord::new(1, 0),
+                Coord::new(0, 1),
+                Coord::new(2, 1),
+                Coord::new(1, 2)
+            ]
+        );
+        assert_eq!(automaton.get(Coord::new(3, 0)), None);
+    }
+}
@@ -0,0 +1,208 @@
+//! Decomposing coordinates in an unbounded world into the coordinate of a fixed-size chunk and
+//! an offset within that chunk.
+//!
+//! Chunks are numbered such that chunk `(0, 0)` covers the cells from the origin up to the
+//! chunk size, and chunk `(-1, -1)` is immediately above and to the left of it. This uses
+//! Euclidean division, like `Coord::normalize`, so local offsets are never negative.
+
+use crate::coord::{Coord, CoordIterRowMajor, Size};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// A world divided into chunks of a fixed, non-empty size.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "ChunkGridFields"))]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ChunkGrid {
+    chunk_size: Size,
+}
+
+/// The shape in which the `Deserialize` implementation of `ChunkGrid` reads its input before
+/// the chunk size is checked to be non-empty.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(rename = "ChunkGrid")]
+struct ChunkGridFields {
+    chunk_size: Size,
+}
+
+#[cfg(feature = "serialize")]
+impl TryFrom<ChunkGridFields> for ChunkGrid {
+    type Error = &'static str;
+    fn try_from(ChunkGridFields { chunk_size }: ChunkGridFields) -> Result<Self, Self::Error> {
+        if chunk_size.is_empty() {
+            Err("chunk size is empty")
+        } else {
+            Ok(ChunkGrid::new(chunk_size))
+        }
+    }
+}
+
+/// A world coordinate split into the chunk containing it and its offset within that chunk.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChunkCoord {
+    pub chunk: Coord,
+    pub local: Coord,
+}
+
+impl ChunkGrid {
+    /// Panics if `chunk_size` is empty.
+    pub const fn new(chunk_size: Size) -> Self {
+        assert!(!chunk_size.is_empty(), "chunk size is empty");
+        Self { chunk_size }
+    }
+
+    pub const fn chunk_size(self) -> Size {
+        self.chunk_size
+    }
+
+    /// The chunk containing `coord` and the offset of `coord` within it.
+    pub const fn split(self, coord: Coord) -> ChunkCoord {
+        let (w, h) = (
+            self.chunk_size.width() as i64,
+            self.chunk_size.height() as i64,
+        );
+        let (x, y) = (coord.x as i64, coord.y as i64);
+        ChunkCoord {
+            // Dividing by a positive divisor brings the result closer to 0 so it still fits,
+            // and the remainder is less than the chunk size which is at most `i32::MAX + 1`.
+            chunk: Coord::new(x.div_euclid(w) as i32, y.div_euclid(h) as i32),
+            local: Coord::new(x.rem_euclid(w) as i32, y.rem_euclid(h) as i32),
+        }
+    }
+
+    /// The world coordinate at offset `local` within `chunk`, or `None` if it can't be
+    /// represented by a `Coord`. `local` may be outside the chunk.
+    pub const fn checked_combine(self, chunk: Coord, local: Coord) -> Option<Coord> {
+        let x = chunk.x as i64 * self.chunk_size.width() as i64 + local.x as i64;
+        let y = chunk.y as i64 * self.chunk_size.height() as i64 + local.y as i64;
+        if x < i32::MIN as i64 || x > i32::MAX as i64 || y < i32::MIN as i64 || y > i32::MAX as i64
+        {
+            None
+        } else {
+            Some(Coord::new(x as i32, y as i32))
+        }
+    }
+
+    /// The world coordinate at offset `local` within `chunk`. `local` may be outside the
+    /// chunk. Panics if the result can't be represented by a `Coord`.
+    pub const fn combine(self, chunk: Coord, local: Coord) -> Coord {
+        match self.checked_combine(chunk, local) {
+            Some(coord) => coord,
+            None => panic!("chunk coordinate is out of range"),
+        }
+    }
+
+    /// The world coordinate of the top-left cell of `chunk`, or `None` if it can't be
+    /// represented by a `Coord`.
+    pub const fn chunk_top_left(self, chunk: Coord) -> Option<Coord> {
+        self.checked_combine(chunk, Coord::new(0, 0))
+    }
+
+    /// Iterates over the chunks which contain any cell of the rectangle with top-left corner
+    /// `top_left` and dimensions `size`, in row-major order. Cells beyond `i32::MAX` are
+    /// ignored as they can't be represented.
+    pub const fn chunks_overlapping(self, top_left: Coord, size: Size) -> ChunksOverlapping {
+        if size.is_empty() {
+            return ChunksOverlapping {
+                first: Coord::new(0, 0),
+                chunks: Size::new(0, 0).coord_iter_row_major(),
+            };
+        }
+        let bottom_right = Coord::new(
+            top_left.x.saturating_add_unsigned(size.width() - 1),
+            top_left.y.saturating_add_unsigned(size.height() - 1),
+        );
+        let first = self.split(top_left).chunk;
+        let last = self.split(bottom_right).chunk;
+        let count = Size::new(last.x.abs_diff(first.x) + 1, last.y.abs_diff(first.y) + 1);
+        ChunksOverlapping {
+            first,
+            chunks: count.coord_iter_row_major(),
+        }
+    }
+}
+
+/// Iterator over chunk coordinates, returned by `ChunkGrid::chunks_overlapping`.
+pub struct ChunksOverlapping {
+    first: Coord,
+    chunks: CoordIterRowMajor,
+}
+
+impl Iterator for ChunksOverlapping {
+    type Item = Coord;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(|offset| self.first + offset)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChunkCoord, ChunkGrid};
+    use crate::coord::{Coord, Size};
+    use proptest::prelude::*;
+
+    #[test]
+    fn negative() {
+        let grid = ChunkGrid::new(Size::new(16, 8));
+        assert_eq!(
+            grid.split(Coord::new(-1, -9)),
+            ChunkCoord {
+                chunk: Coord::new(-1, -2),
+                local: Coord::new(15, 7),
+            }
+        );
+        assert_eq!(grid.split(Coord::new(16, 0)).chunk, Coord::new(1, 0));
+        assert_eq!(
+            grid.combine(Coord::new(-1, -2), Coord::new(15, 7)),
+            Coord::new(-1, -9)
+        );
+        assert_eq!(
+            grid.chunk_top_left(Coord::new(-2, 3)),
+            Some(Coord::new(-32, 24))
+        );
+        assert_eq!(grid.chunk_top_left(Coord::new(i32::MAX, 0)), None);
+    }
+
+    #[test]
+    fn overlapping() {
+        let grid = ChunkGrid::new(Size::new(10, 10));
+        let chunks = grid
+            .chunks_overlapping(Coord::new(-5, 5), Size::new(20, 5))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chunks,
+            [Coord::new(-1, 0), Coord::new(0, 0), Coord::new(1, 0)]
+        );
+        assert_eq!(
+            grid.chunks_overlapping(Coord::new(0, 0), Size::new(0, 5))
+                .count(),
+            0
+        );
+        assert_eq!(
+            grid.chunks_overlapping(Coord::new(i32::MAX, 0), Size::new(100, 1))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn deserialize() {
+        let grid = ChunkGrid::new(Size::new(16, 8));
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(serde_json::from_str::<ChunkGrid>(&json).unwrap(), grid);
+        let e = serde_json::from_str::<ChunkGrid>(r#"{"chunk_size":{"x":0,"y":0}}"#).unwrap_err();
+        assert!(e.to_string().contains("chunk size is empty"));
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(coord in any::<Coord>(), chunk_size in any::<Size>().prop_filter("non-empty", |s| !s.is_empty())) {
+            let grid = ChunkGrid::new(chunk_size);
+            let ChunkCoord { chunk, local } = grid.split(coord);
+            prop_assert!(local.is_valid(grid.chunk_size()));
+            prop_assert_eq!(grid.combine(chunk, local), coord);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::{DimensionTooLargeForCoord, DimensionTooLargeForSize, NegativeDimension};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 
@@ -31,7 +32,7 @@ impl Axis {
             Axis::Y => Size::try_new(other_axis, this_axis),
         }
     }
-    pub fn new_size(self, this_axis: u32, other_axis: u32) -> Size {
+    pub const fn new_size(self, this_axis: u32, other_axis: u32) -> Size {
         match self {
             Axis::X => Size::new(this_axis, other_axis),
             Axis::Y => Size::new(other_axis, this_axis),
@@ -39,7 +40,11 @@ impl Axis {
     }
 }
 
-pub trait StaticAxis: private::Sealed {
+/// Implemented by the unit types `static_axis::X` and `static_axis::Y`, for writing code which is
+/// generic over the axis it operates along.
+pub trait StaticAxis:
+    private::Sealed + Copy + Default + core::fmt::Debug + core::hash::Hash + Eq + Ord
+{
     type Other: StaticAxis;
     fn axis() -> Axis;
     fn new_coord(this_axis: i32, other_axis: i32) -> Coord;
@@ -56,41 +61,33 @@ pub trait StaticAxis: private::Sealed {
     fn try_size_set_in_place(size: &mut Size, value: u32) -> Result<(), DimensionTooLargeForSize>;
     fn size_set(size: Size, value: u32) -> Size {
         match Self::try_size_set(size, value) {
-            Err(DimensionTooLargeForSize) => {
-                panic!("Value is too big: {}. Max is {}.", value, MAX_SIZE_FIELD);
-            }
+            Err(e) => panic!("{}", e),
             Ok(size) => size,
         }
     }
     fn size_set_in_place(size: &mut Size, value: u32) {
-        match Self::try_size_set_in_place(size, value) {
-            Err(DimensionTooLargeForSize) => {
-                panic!("Value is too big: {}. Max is {}.", value, MAX_SIZE_FIELD);
-            }
-            Ok(()) => (),
+        if let Err(e) = Self::try_size_set_in_place(size, value) {
+            panic!("{}", e);
         }
     }
     fn new_size(this_axis: u32, other_axis: u32) -> Size {
         match Self::try_new_size(this_axis, other_axis) {
-            Err(DimensionTooLargeForSize) => {
-                panic!(
-                    "Size is too big: ({}, {}). Max is {}.",
-                    this_axis, other_axis, MAX_SIZE_FIELD
-                );
-            }
+            Err(e) => panic!("{}", e),
             Ok(size) => size,
         }
     }
 }
 
 pub mod static_axis {
+    #[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
     pub struct X;
+    #[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
     pub struct Y;
 }
 
-const fn check_size_limit(value: u32) -> Result<(), DimensionTooLargeForSize> {
+const fn check_size_limit(value: u32, axis: Axis) -> Result<(), DimensionTooLargeForSize> {
     if value > MAX_SIZE_FIELD {
-        Err(DimensionTooLargeForSize)
+        Err(DimensionTooLargeForSize { axis, value })
     } else {
         Ok(())
     }
@@ -138,11 +135,11 @@ impl StaticAxis for static_axis::X {
         }
     }
     fn try_size_set(size: Size, value: u32) -> Result<Size, DimensionTooLargeForSize> {
-        check_size_limit(value)?;
+        check_size_limit(value, Axis::X)?;
         Ok(Size { x: value, ..size })
     }
     fn try_size_set_in_place(size: &mut Size, value: u32) -> Result<(), DimensionTooLargeForSize> {
-        check_size_limit(value)?;
+        check_size_limit(value, Axis::X)?;
         size.x = value;
         Ok(())
     }
@@ -187,11 +184,11 @@ impl StaticAxis for static_axis::Y {
         }
     }
     fn try_size_set(size: Size, value: u32) -> Result<Size, DimensionTooLargeForSize> {
-        check_size_limit(value)?;
+        check_size_limit(value, Axis::Y)?;
         Ok(Size { y: value, ..size })
     }
     fn try_size_set_in_place(size: &mut Size, value: u32) -> Result<(), DimensionTooLargeForSize> {
-        check_size_limit(value)?;
+        check_size_limit(value, Axis::Y)?;
         size.y = value;
         Ok(())
     }
@@ -208,26 +205,29 @@ mod private {
 }
 
 /// General purpose coordinate
+///
+/// The layout is guaranteed to be `#[repr(C)]`: `x` followed by `y`, with no padding, the
+/// same as `[i32; 2]`.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default, PartialOrd, Ord)]
+#[repr(C)]
 pub struct Coord {
     pub x: i32,
     pub y: i32,
 }
 
-#[derive(Debug)]
-pub struct NegativeDimension;
-
-#[derive(Debug)]
-pub struct DimensionTooLargeForSize;
-
-#[derive(Debug)]
-pub struct DimensionTooLargeForCoord;
-
 impl Coord {
     pub const fn new(x: i32, y: i32) -> Self {
         Self { x, y }
     }
+    /// Like the `From<(i32, i32)>` impl, but const.
+    pub const fn from_tuple((x, y): (i32, i32)) -> Self {
+        Self::new(x, y)
+    }
+    /// Like the `From<[i32; 2]>` impl, but const.
+    pub const fn from_array([x, y]: [i32; 2]) -> Self {
+        Self::new(x, y)
+    }
     pub const fn from_size(size: Size) -> Result<Self, DimensionTooLargeForCoord> {
         size.to_coord()
     }
@@ -237,20 +237,28 @@ impl Coord {
         let y = rng.gen_range(0..size.height() as i32);
         Self { x, y }
     }
-    pub fn to_size(self) -> Result<Size, NegativeDimension> {
-        if self.x < 0 || self.y < 0 {
-            Err(NegativeDimension)
+    pub const fn to_size(self) -> Result<Size, NegativeDimension> {
+        if self.x < 0 {
+            Err(NegativeDimension {
+                axis: Axis::X,
+                value: self.x,
+            })
+        } else if self.y < 0 {
+            Err(NegativeDimension {
+                axis: Axis::Y,
+                value: self.y,
+            })
         } else {
-            Ok(Size::new(self.x as u32, self.y as u32))
+            // Non-negative `i32`s are always within `MAX_SIZE_FIELD`.
+            Ok(Size {
+                x: self.x as u32,
+                y: self.y as u32,
+            })
         }
     }
     const fn normalize_part(value: i32, size: u32) -> i32 {
-        let value = value % size as i32;
-        if value < 0 {
-            value + size as i32
-        } else {
-            value
-        }
+        // `size` may be `MAX_SIZE_FIELD`, which doesn't fit in an `i32`.
+        (value as i64).rem_euclid(size as i64) as i32
     }
     pub const fn normalize(self, size: Size) -> Self {
         Self {
@@ -297,7 +305,7 @@ impl Coord {
             Axis::Y => self.y,
         }
     }
-    pub fn get_mut(&mut self, axis: Axis) -> &mut i32 {
+    pub const fn get_mut(&mut self, axis: Axis) -> &mut i32 {
         match axis {
             Axis::X => &mut self.x,
             Axis::Y => &mut self.y,
@@ -323,7 +331,7 @@ impl Coord {
             Axis::Y => Self::new(self.x, value),
         }
     }
-    pub fn set_in_place(&mut self, axis: Axis, value: i32) {
+    pub const fn set_in_place(&mut self, axis: Axis, value: i32) {
         match axis {
             Axis::X => self.x = value,
             Axis::Y => self.y = value,
@@ -358,51 +366,160 @@ impl Coord {
     pub const fn set_y(self, y: i32) -> Self {
         Self { y, ..self }
     }
-    pub fn set_x_in_place(&mut self, x: i32) {
+    pub const fn set_x_in_place(&mut self, x: i32) {
         self.x = x;
     }
-    pub fn set_y_in_place(&mut self, y: i32) {
+    pub const fn set_y_in_place(&mut self, y: i32) {
         self.y = y;
     }
-    pub fn checked_add(self, rhs: Self) -> Option<Self> {
-        self.x
-            .checked_add(rhs.x)
-            .and_then(|x| self.y.checked_add(rhs.y).map(|y| Self::new(x, y)))
+    /// Combines the results of a checked operation on each component.
+    const fn checked_from_parts(x: Option<i32>, y: Option<i32>) -> Option<Self> {
+        match (x, y) {
+            (Some(x), Some(y)) => Some(Self::new(x, y)),
+            _ => None,
+        }
     }
-    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
-        self.x
-            .checked_sub(rhs.x)
-            .and_then(|x| self.y.checked_sub(rhs.y).map(|y| Self::new(x, y)))
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_add(rhs.x), self.y.checked_add(rhs.y))
     }
-    pub fn checked_mul(self, rhs: i32) -> Option<Self> {
-        self.x
-            .checked_mul(rhs)
-            .and_then(|x| self.y.checked_mul(rhs).map(|y| Self::new(x, y)))
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_sub(rhs.x), self.y.checked_sub(rhs.y))
     }
-    pub fn checked_div(self, rhs: i32) -> Option<Self> {
-        self.x
-            .checked_div(rhs)
-            .and_then(|x| self.y.checked_div(rhs).map(|y| Self::new(x, y)))
+    pub const fn checked_mul(self, rhs: i32) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_mul(rhs), self.y.checked_mul(rhs))
+    }
+    pub const fn checked_div(self, rhs: i32) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_div(rhs), self.y.checked_div(rhs))
+    }
+    pub const fn checked_rem(self, rhs: i32) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_rem(rhs), self.y.checked_rem(rhs))
+    }
+    pub const fn checked_neg(self) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_neg(), self.y.checked_neg())
+    }
+    pub const fn checked_add_size(self, rhs: Size) -> Option<Self> {
+        Self::checked_from_parts(
+            self.x.checked_add_unsigned(rhs.x),
+            self.y.checked_add_unsigned(rhs.y),
+        )
+    }
+    pub const fn checked_sub_size(self, rhs: Size) -> Option<Self> {
+        Self::checked_from_parts(
+            self.x.checked_sub_unsigned(rhs.x),
+            self.y.checked_sub_unsigned(rhs.y),
+        )
+    }
+    pub const fn wrapping_add(self, rhs: Self) -> Self {
+        Self::new(self.x.wrapping_add(rhs.x), self.y.wrapping_add(rhs.y))
+    }
+    pub const fn wrapping_sub(self, rhs: Self) -> Self {
+        Self::new(self.x.wrapping_sub(rhs.x), self.y.wrapping_sub(rhs.y))
+    }
+    pub const fn wrapping_mul(self, rhs: i32) -> Self {
+        Self::new(self.x.wrapping_mul(rhs), self.y.wrapping_mul(rhs))
+    }
+    pub const fn wrapping_div(self, rhs: i32) -> Self {
+        Self::new(self.x.wrapping_div(rhs), self.y.wrapping_div(rhs))
+    }
+    pub const fn wrapping_neg(self) -> Self {
+        Self::new(self.x.wrapping_neg(), self.y.wrapping_neg())
+    }
+    pub const fn wrapping_add_size(self, rhs: Size) -> Self {
+        Self::new(
+            self.x.wrapping_add_unsigned(rhs.x),
+            self.y.wrapping_add_unsigned(rhs.y),
+        )
+    }
+    pub const fn wrapping_sub_size(self, rhs: Size) -> Self {
+        Self::new(
+            self.x.wrapping_sub_unsigned(rhs.x),
+            self.y.wrapping_sub_unsigned(rhs.y),
+        )
+    }
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self::new(self.x.saturating_add(rhs.x), self.y.saturating_add(rhs.y))
+    }
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self::new(self.x.saturating_sub(rhs.x), self.y.saturating_sub(rhs.y))
+    }
+    pub const fn saturating_mul(self, rhs: i32) -> Self {
+        Self::new(self.x.saturating_mul(rhs), self.y.saturating_mul(rhs))
+    }
+    pub const fn saturating_neg(self) -> Self {
+        Self::new(self.x.saturating_neg(), self.y.saturating_neg())
+    }
+    pub const fn saturating_add_size(self, rhs: Size) -> Self {
+        Self::new(
+            self.x.saturating_add_unsigned(rhs.x),
+            self.y.saturating_add_unsigned(rhs.y),
+        )
+    }
+    pub const fn saturating_sub_size(self, rhs: Size) -> Self {
+        Self::new(
+            self.x.saturating_sub_unsigned(rhs.x),
+            self.y.saturating_sub_unsigned(rhs.y),
+        )
+    }
+    /// Component-wise euclidean division. Panics if either component of `rhs` is 0.
+    pub const fn div_euclid(self, rhs: Self) -> Self {
+        Self::new(self.x.div_euclid(rhs.x), self.y.div_euclid(rhs.y))
     }
+    /// Component-wise euclidean remainder. Each component of the result is non-negative.
+    /// Panics if either component of `rhs` is 0.
+    pub const fn rem_euclid(self, rhs: Self) -> Self {
+        Self::new(self.x.rem_euclid(rhs.x), self.y.rem_euclid(rhs.y))
+    }
+    /// The difference between two coordinates along each axis, as absolute values. This
+    /// can't overflow, unlike subtracting the coordinates.
+    const fn abs_diff(self, other: Self) -> (u32, u32) {
+        (self.x.abs_diff(other.x), self.y.abs_diff(other.y))
+    }
+    const fn checked_sum_of_squares((x, y): (u32, u32)) -> Option<u32> {
+        match (x.checked_mul(x), y.checked_mul(y)) {
+            (Some(x2), Some(y2)) => x2.checked_add(y2),
+            _ => None,
+        }
+    }
+    /// Panics if the result doesn't fit in a `u32`, including in release builds.
     pub const fn magnitude2(self) -> u32 {
-        (self.x * self.x) as u32 + (self.y * self.y) as u32
+        match self.checked_magnitude2() {
+            Some(magnitude2) => magnitude2,
+            None => panic!("squared magnitude doesn't fit in a u32"),
+        }
     }
+    /// Panics if the result doesn't fit in a `u32`, including in release builds.
     pub const fn distance2(self, other: Self) -> u32 {
-        Self {
-            x: self.x - other.x,
-            y: self.y - other.y,
+        match self.checked_distance2(other) {
+            Some(distance2) => distance2,
+            None => panic!("squared distance doesn't fit in a u32"),
         }
-        .magnitude2()
     }
+    pub const fn checked_magnitude2(self) -> Option<u32> {
+        Self::checked_sum_of_squares((self.x.unsigned_abs(), self.y.unsigned_abs()))
+    }
+    pub const fn checked_distance2(self, other: Self) -> Option<u32> {
+        Self::checked_sum_of_squares(self.abs_diff(other))
+    }
+    /// Panics if the result doesn't fit in a `u32`, including in release builds.
     pub const fn manhattan_magnitude(self) -> u32 {
-        self.x.abs() as u32 + self.y.abs() as u32
+        match self.checked_manhattan_magnitude() {
+            Some(magnitude) => magnitude,
+            None => panic!("manhattan magnitude doesn't fit in a u32"),
+        }
     }
+    /// Panics if the result doesn't fit in a `u32`, including in release builds.
     pub const fn manhattan_distance(self, other: Self) -> u32 {
-        Self {
-            x: self.x - other.x,
-            y: self.y - other.y,
+        match self.checked_manhattan_distance(other) {
+            Some(distance) => distance,
+            None => panic!("manhattan distance doesn't fit in a u32"),
         }
-        .manhattan_magnitude()
+    }
+    pub const fn checked_manhattan_magnitude(self) -> Option<u32> {
+        self.x.unsigned_abs().checked_add(self.y.unsigned_abs())
+    }
+    pub const fn checked_manhattan_distance(self, other: Self) -> Option<u32> {
+        let (x, y) = self.abs_diff(other);
+        x.checked_add(y)
     }
     pub const fn opposite(self) -> Self {
         Self {
@@ -451,17 +568,17 @@ impl Coord {
         self.x == 0 && self.y == 0
     }
 
-    pub fn pairwise_max(self, other: Self) -> Self {
+    pub const fn pairwise_max(self, other: Self) -> Self {
         Self {
-            x: self.x.max(other.x),
-            y: self.y.max(other.y),
+            x: if self.x > other.x { self.x } else { other.x },
+            y: if self.y > other.y { self.y } else { other.y },
         }
     }
 
-    pub fn pairwise_min(self, other: Self) -> Self {
+    pub const fn pairwise_min(self, other: Self) -> Self {
         Self {
-            x: self.x.min(other.x),
-            y: self.y.min(other.y),
+            x: if self.x < other.x { self.x } else { other.x },
+            y: if self.y < other.y { self.y } else { other.y },
         }
     }
 
@@ -474,27 +591,36 @@ impl Coord {
 }
 
 impl From<(i32, i32)> for Coord {
-    fn from((x, y): (i32, i32)) -> Self {
-        Coord::new(x, y)
+    fn from(tuple: (i32, i32)) -> Self {
+        Coord::from_tuple(tuple)
     }
 }
 
 impl From<[i32; 2]> for Coord {
     fn from(array: [i32; 2]) -> Self {
-        Coord::new(array[0], array[1])
+        Coord::from_array(array)
     }
 }
 
 /// A size cannot be created which would contain un-addressable cells.
 /// That is, the maximum size has a width and height of one greater than the maximum `i32`.
+/// When deserializing, dimensions greater than `MAX_SIZE_FIELD` are rejected.
+///
+/// The layout is guaranteed to be `#[repr(C)]`: the width followed by the height, with no
+/// padding, the same as `[u32; 2]`.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    serde(try_from = "crate::serde_repr::SizeFields")
+)]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default, PartialOrd, Ord)]
+#[repr(C)]
 pub struct Size {
     x: u32,
     y: u32,
 }
 
-pub const MAX_SIZE_FIELD: u32 = ::core::i32::MAX as u32 + 1;
+pub const MAX_SIZE_FIELD: u32 = i32::MAX as u32 + 1;
 
 pub const MAX_SIZE: Size = Size {
     x: MAX_SIZE_FIELD,
@@ -503,10 +629,10 @@ pub const MAX_SIZE: Size = Size {
 
 impl Size {
     pub const fn try_new(width: u32, height: u32) -> Result<Self, DimensionTooLargeForSize> {
-        if let Err(e) = check_size_limit(width) {
+        if let Err(e) = check_size_limit(width, Axis::X) {
             return Err(e);
         }
-        if let Err(e) = check_size_limit(height) {
+        if let Err(e) = check_size_limit(height, Axis::Y) {
             return Err(e);
         }
         Ok(Self {
@@ -516,20 +642,27 @@ impl Size {
     }
 
     /// Creates a new `Size`.
-    /// Panics if `width` or `width` is greater than `::core::i32::MAX as u32 + 1`.
-    pub fn new(width: u32, height: u32) -> Self {
+    /// Panics if `width` or `height` is greater than `i32::MAX as u32 + 1`.
+    pub const fn new(width: u32, height: u32) -> Self {
         match Self::try_new(width, height) {
-            Err(DimensionTooLargeForSize) => {
-                panic!(
-                    "Size is too big: ({}, {}). Max is {}.",
-                    width, width, MAX_SIZE_FIELD
-                );
-            }
+            Err(e) => e.panic(),
             Ok(size) => size,
         }
     }
 
-    /// Like new, but const and never panics as it's impossible to construct an invalid size
+    /// Like the `From<(u32, u32)>` impl, but const.
+    /// Panics if `width` or `height` is greater than `i32::MAX as u32 + 1`.
+    pub const fn from_tuple((width, height): (u32, u32)) -> Self {
+        Self::new(width, height)
+    }
+
+    /// Like the `From<[u32; 2]>` impl, but const.
+    /// Panics if `width` or `height` is greater than `i32::MAX as u32 + 1`.
+    pub const fn from_array([width, height]: [u32; 2]) -> Self {
+        Self::new(width, height)
+    }
+
+    /// Like new, but never panics as it's impossible to construct an invalid size
     pub const fn new_u16(width: u16, height: u16) -> Self {
         Self {
             x: width as u32,
@@ -537,13 +670,21 @@ impl Size {
         }
     }
 
-    pub fn from_coord(coord: Coord) -> Result<Self, NegativeDimension> {
+    pub const fn from_coord(coord: Coord) -> Result<Self, NegativeDimension> {
         coord.to_size()
     }
 
     pub const fn to_coord(self) -> Result<Coord, DimensionTooLargeForCoord> {
-        if self.x > ::core::i32::MAX as u32 || self.y > ::core::i32::MAX as u32 {
-            Err(DimensionTooLargeForCoord)
+        if self.x > i32::MAX as u32 {
+            Err(DimensionTooLargeForCoord {
+                axis: Axis::X,
+                value: self.x,
+            })
+        } else if self.y > i32::MAX as u32 {
+            Err(DimensionTooLargeForCoord {
+                axis: Axis::Y,
+                value: self.y,
+            })
         } else {
             Ok(Coord::new(self.x as i32, self.y as i32))
         }
@@ -556,7 +697,7 @@ impl Size {
         }
     }
 
-    pub fn get_mut(&mut self, axis: Axis) -> &mut u32 {
+    pub const fn get_mut(&mut self, axis: Axis) -> &mut u32 {
         match axis {
             Axis::X => &mut self.x,
             Axis::Y => &mut self.y,
@@ -577,7 +718,7 @@ impl Size {
     }
 
     pub const fn try_set(self, axis: Axis, value: u32) -> Result<Self, DimensionTooLargeForSize> {
-        if let Err(e) = check_size_limit(value) {
+        if let Err(e) = check_size_limit(value, axis) {
             return Err(e);
         }
         Ok(match axis {
@@ -593,21 +734,21 @@ impl Size {
     }
 
     #[must_use]
-    pub fn set(self, axis: Axis, value: u32) -> Self {
+    pub const fn set(self, axis: Axis, value: u32) -> Self {
         match self.try_set(axis, value) {
-            Err(DimensionTooLargeForSize) => {
-                panic!("Value is too big: {}. Max is {}.", value, MAX_SIZE_FIELD);
-            }
+            Err(e) => e.panic(),
             Ok(size) => size,
         }
     }
 
-    pub fn try_set_in_place(
+    pub const fn try_set_in_place(
         &mut self,
         axis: Axis,
         value: u32,
     ) -> Result<(), DimensionTooLargeForSize> {
-        check_size_limit(value)?;
+        if let Err(e) = check_size_limit(value, axis) {
+            return Err(e);
+        }
         match axis {
             Axis::X => self.x = value,
             Axis::Y => self.y = value,
@@ -615,12 +756,9 @@ impl Size {
         Ok(())
     }
 
-    pub fn set_in_place(&mut self, axis: Axis, value: u32) {
-        match self.try_set_in_place(axis, value) {
-            Err(DimensionTooLargeForSize) => {
-                panic!("Value is too big: {}. Max is {}.", value, MAX_SIZE_FIELD);
-            }
-            Ok(()) => (),
+    pub const fn set_in_place(&mut self, axis: Axis, value: u32) {
+        if let Err(e) = self.try_set_in_place(axis, value) {
+            e.panic();
         }
     }
 
@@ -632,7 +770,7 @@ impl Size {
         axis.try_new_size(this_axis, other_axis)
     }
 
-    pub fn new_axis(this_axis: u32, other_axis: u32, axis: Axis) -> Self {
+    pub const fn new_axis(this_axis: u32, other_axis: u32, axis: Axis) -> Self {
         axis.new_size(this_axis, other_axis)
     }
 
@@ -678,38 +816,44 @@ impl Size {
         A::new_size(this_axis, other_axis)
     }
 
-    pub fn try_set_width(self, width: u32) -> Result<Self, DimensionTooLargeForSize> {
-        self.try_set_static::<static_axis::X>(width)
+    pub const fn try_set_width(self, width: u32) -> Result<Self, DimensionTooLargeForSize> {
+        self.try_set(Axis::X, width)
     }
 
-    pub fn try_set_height(self, height: u32) -> Result<Self, DimensionTooLargeForSize> {
-        self.try_set_static::<static_axis::Y>(height)
+    pub const fn try_set_height(self, height: u32) -> Result<Self, DimensionTooLargeForSize> {
+        self.try_set(Axis::Y, height)
     }
 
     #[must_use]
-    pub fn set_width(self, width: u32) -> Self {
-        self.set_static::<static_axis::X>(width)
+    pub const fn set_width(self, width: u32) -> Self {
+        self.set(Axis::X, width)
     }
 
     #[must_use]
-    pub fn set_height(self, height: u32) -> Self {
-        self.set_static::<static_axis::Y>(height)
+    pub const fn set_height(self, height: u32) -> Self {
+        self.set(Axis::Y, height)
     }
 
-    pub fn try_set_width_in_place(&mut self, width: u32) -> Result<(), DimensionTooLargeForSize> {
-        self.try_set_static_in_place::<static_axis::X>(width)
+    pub const fn try_set_width_in_place(
+        &mut self,
+        width: u32,
+    ) -> Result<(), DimensionTooLargeForSize> {
+        self.try_set_in_place(Axis::X, width)
     }
 
-    pub fn try_set_height_in_place(&mut self, height: u32) -> Result<(), DimensionTooLargeForSize> {
-        self.try_set_static_in_place::<static_axis::Y>(height)
+    pub const fn try_set_height_in_place(
+        &mut self,
+        height: u32,
+    ) -> Result<(), DimensionTooLargeForSize> {
+        self.try_set_in_place(Axis::Y, height)
     }
 
-    pub fn set_width_in_place(&mut self, width: u32) {
-        self.set_static_in_place::<static_axis::X>(width)
+    pub const fn set_width_in_place(&mut self, width: u32) {
+        self.set_in_place(Axis::X, width)
     }
 
-    pub fn set_height_in_place(&mut self, height: u32) {
-        self.set_static_in_place::<static_axis::Y>(height)
+    pub const fn set_height_in_place(&mut self, height: u32) {
+        self.set_in_place(Axis::Y, height)
     }
 
     /// Returns the width.
@@ -741,16 +885,89 @@ impl Size {
         (self.x * self.y) as usize
     }
 
-    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
-        self.x
-            .checked_sub(rhs.x)
-            .and_then(|x| self.y.checked_sub(rhs.y).map(|y| Self::new(x, y)))
+    /// Combines the results of a checked operation on each dimension, rejecting dimensions
+    /// greater than `MAX_SIZE_FIELD`.
+    const fn checked_from_parts(x: Option<u32>, y: Option<u32>) -> Option<Self> {
+        match (x, y) {
+            (Some(x), Some(y)) => match Self::try_new(x, y) {
+                Ok(size) => Some(size),
+                Err(_) => None,
+            },
+            _ => None,
+        }
     }
 
-    pub fn saturating_sub(self, rhs: Self) -> Self {
-        let x = self.x.saturating_sub(rhs.x);
-        let y = self.y.saturating_sub(rhs.y);
-        Self::new(x, y)
+    const fn saturate_field(value: u32) -> u32 {
+        if value > MAX_SIZE_FIELD {
+            MAX_SIZE_FIELD
+        } else {
+            value
+        }
+    }
+
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_sub(rhs.x), self.y.checked_sub(rhs.y))
+    }
+
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(rhs.x),
+            y: self.y.saturating_sub(rhs.y),
+        }
+    }
+
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_add(rhs.x), self.y.checked_add(rhs.y))
+    }
+
+    pub const fn checked_mul(self, rhs: u32) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_mul(rhs), self.y.checked_mul(rhs))
+    }
+
+    pub const fn checked_div(self, rhs: u32) -> Option<Self> {
+        Self::checked_from_parts(self.x.checked_div(rhs), self.y.checked_div(rhs))
+    }
+
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self {
+            x: Self::saturate_field(self.x.saturating_add(rhs.x)),
+            y: Self::saturate_field(self.y.saturating_add(rhs.y)),
+        }
+    }
+
+    pub const fn saturating_mul(self, rhs: u32) -> Self {
+        Self {
+            x: Self::saturate_field(self.x.saturating_mul(rhs)),
+            y: Self::saturate_field(self.y.saturating_mul(rhs)),
+        }
+    }
+
+    /// Wraps each dimension like `u32::wrapping_add`, then reduces any dimension greater than
+    /// `MAX_SIZE_FIELD` to `MAX_SIZE_FIELD` so the result is always a valid `Size`. Dimensions
+    /// which are valid after wrapping are the same as with `u32` arithmetic.
+    pub const fn wrapping_add(self, rhs: Self) -> Self {
+        Self {
+            x: Self::saturate_field(self.x.wrapping_add(rhs.x)),
+            y: Self::saturate_field(self.y.wrapping_add(rhs.y)),
+        }
+    }
+
+    /// Wraps each dimension like `u32::wrapping_sub`, then reduces any dimension greater than
+    /// `MAX_SIZE_FIELD` to `MAX_SIZE_FIELD`, like `wrapping_add`.
+    pub const fn wrapping_sub(self, rhs: Self) -> Self {
+        Self {
+            x: Self::saturate_field(self.x.wrapping_sub(rhs.x)),
+            y: Self::saturate_field(self.y.wrapping_sub(rhs.y)),
+        }
+    }
+
+    /// Wraps each dimension like `u32::wrapping_mul`, then reduces any dimension greater than
+    /// `MAX_SIZE_FIELD` to `MAX_SIZE_FIELD`, like `wrapping_add`.
+    pub const fn wrapping_mul(self, rhs: u32) -> Self {
+        Self {
+            x: Self::saturate_field(self.x.wrapping_mul(rhs)),
+            y: Self::saturate_field(self.y.wrapping_mul(rhs)),
+        }
     }
 
     pub const fn max_field() -> u32 {
@@ -777,17 +994,17 @@ impl Size {
         CoordIterRowMajor::new(self)
     }
 
-    pub fn pairwise_max(self, other: Self) -> Self {
+    pub const fn pairwise_max(self, other: Self) -> Self {
         Self {
-            x: self.x.max(other.x),
-            y: self.y.max(other.y),
+            x: if self.x > other.x { self.x } else { other.x },
+            y: if self.y > other.y { self.y } else { other.y },
         }
     }
 
-    pub fn pairwise_min(self, other: Self) -> Self {
+    pub const fn pairwise_min(self, other: Self) -> Self {
         Self {
-            x: self.x.min(other.x),
-            y: self.y.min(other.y),
+            x: if self.x < other.x { self.x } else { other.x },
+            y: if self.y < other.y { self.y } else { other.y },
         }
     }
 
@@ -867,6 +1084,7 @@ mod edge_iter {
 
     type TwoDimensional = Chain<Chain<Chain<Top, Right>, Bottom>, Left>;
 
+    #[allow(clippy::enum_variant_names)]
     enum IterPrivate {
         ZeroDimensional,
         OneDimensional(CoordIterRowMajor),
@@ -922,17 +1140,18 @@ mod edge_iter {
 pub use edge_iter::Iter as EdgeIter;
 
 impl From<(u32, u32)> for Size {
-    fn from((x, y): (u32, u32)) -> Self {
-        Size::new(x, y)
+    fn from(tuple: (u32, u32)) -> Self {
+        Size::from_tuple(tuple)
     }
 }
 
 impl From<[u32; 2]> for Size {
     fn from(array: [u32; 2]) -> Self {
-        Size::new(array[0], array[1])
+        Size::from_array(array)
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct CoordIterRowMajor {
     coord: Coord,
     size: Size,
@@ -950,7 +1169,8 @@ impl CoordIterRowMajor {
 impl Iterator for CoordIterRowMajor {
     type Item = Coord;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.coord.y == self.size.height() as i32 {
+        // A size with zero width and non-zero height contains no coordinates.
+        if self.coord.y == self.size.height() as i32 || self.size.width() == 0 {
             return None;
         }
         let coord = self.coord;
@@ -965,7 +1185,11 @@ impl Iterator for CoordIterRowMajor {
 
 #[cfg(test)]
 mod test {
-    use super::{Coord, Size};
+    use super::{Coord, Size, MAX_SIZE_FIELD};
+    use crate::arbitrary_impls::with_proptest::size_up_to;
+    use crate::test_strategies::coord_in;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
 
     #[test]
     fn normalize() {
@@ -982,6 +1206,22 @@ mod test {
     #[test]
     fn manhattan_dsitance() {
         assert_eq!(Coord::new(-2, 4).manhattan_distance(Coord::new(5, -2)), 13);
+        let (min, max) = (
+            Coord::new(i32::MIN, i32::MIN),
+            Coord::new(i32::MAX, i32::MAX),
+        );
+        assert_eq!(min.checked_manhattan_distance(max), None);
+        assert_eq!(min.checked_manhattan_magnitude(), None);
+        assert_eq!(
+            Coord::new(i32::MIN, 0).checked_manhattan_distance(Coord::new(i32::MAX, 0)),
+            Some(u32::MAX)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "manhattan distance doesn't fit in a u32")]
+    fn manhattan_distance_overflow() {
+        Coord::new(i32::MIN, i32::MIN).manhattan_distance(Coord::new(i32::MAX, i32::MAX));
     }
 
     #[test]
@@ -1008,7 +1248,6 @@ mod test {
     #[test]
     #[cfg(feature = "std")]
     fn edge_iter() {
-        use std::collections::BTreeSet;
         fn test(size: Size) {
             let brute_forced = size
                 .coord_iter_row_major()
@@ -1022,10 +1261,199 @@ mod test {
             );
         }
         test(Size::new(0, 0));
+        test(Size::new(0, 3));
+        test(Size::new(3, 0));
         test(Size::new(1, 1));
         test(Size::new(1, 3));
         test(Size::new(3, 1));
         test(Size::new(2, 2));
         test(Size::new(3, 5));
     }
+
+    #[test]
+    fn const_api() {
+        const SCREEN: Size = Size::new(80, 24);
+        const PANEL: Size = SCREEN.set_width(20).saturating_sub(Size::new(0, 2));
+        const CENTRE: Coord = match SCREEN.const_div(2).to_coord() {
+            Ok(coord) => coord,
+            Err(_) => panic!(),
+        };
+        const CORNERS: [Coord; 4] = [
+            Coord::new(0, 0),
+            Coord::new(0, 0)
+                .const_add_size(PANEL)
+                .const_sub(Coord::new(1, 1)),
+            CENTRE.const_neg().pairwise_min(Coord::new(-1, -1)),
+            CENTRE.const_mul(2).const_rem_coord(Coord::new(7, 5)),
+        ];
+        const fn grown(mut size: Size) -> Size {
+            size.set_height_in_place(size.height() + 1);
+            *size.get_mut(crate::Axis::X) += 1;
+            size
+        }
+        const GROWN: Size = grown(PANEL);
+        const CHECKED: Option<Coord> = CENTRE.checked_mul(i32::MAX);
+        assert_eq!(PANEL, Size::new(20, 22));
+        assert_eq!(CENTRE, Coord::new(40, 12));
+        assert_eq!(
+            CORNERS,
+            [
+                Coord::new(0, 0),
+                Coord::new(19, 21),
+                Coord::new(-40, -12),
+                Coord::new(3, 4),
+            ]
+        );
+        assert_eq!(GROWN, Size::new(21, 23));
+        assert_eq!(CHECKED, None);
+    }
+
+    #[test]
+    fn const_from_tuple_and_array() {
+        const COORD: Coord = Coord::from_tuple((3, -4));
+        const SIZE: Size = Size::from_array([5, 6]);
+        assert_eq!(COORD, Coord::from([3, -4]));
+        assert_eq!(SIZE, Size::from((5, 6)));
+    }
+
+    #[test]
+    #[should_panic(expected = "height 4000000000 is too large for a size (max 2147483648)")]
+    fn size_too_large_message() {
+        Size::new(1, 4_000_000_000);
+    }
+
+    proptest! {
+        #[test]
+        fn normalize_is_valid(coord in any::<Coord>(), size in any::<Size>().prop_filter("non-empty", |size| !size.is_empty())) {
+            let normalized = coord.normalize(size);
+            prop_assert!(normalized.is_valid(size));
+            prop_assert_eq!(normalized.normalize(size), normalized);
+            prop_assert_eq!(
+                (normalized.x as i64 - coord.x as i64) % size.width() as i64,
+                0
+            );
+        }
+
+        #[test]
+        fn constrain_is_idempotent(coord in any::<Coord>(), size in any::<Size>()) {
+            match coord.constrain(size) {
+                None => prop_assert!(size.is_empty()),
+                Some(constrained) => {
+                    prop_assert!(constrained.is_valid(size));
+                    prop_assert_eq!(constrained.constrain(size), Some(constrained));
+                    if coord.is_valid(size) {
+                        prop_assert_eq!(constrained, coord);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn edge_iter_yields_each_edge_coord_once(size in size_up_to(Size::new(39, 39))) {
+            let via_iter = size.edge_iter().collect::<Vec<_>>();
+            let unique = via_iter.iter().cloned().collect::<BTreeSet<_>>();
+            prop_assert_eq!(unique.len(), via_iter.len());
+            let brute_forced = size
+                .coord_iter_row_major()
+                .filter(|&c| size.is_on_edge(c))
+                .collect::<BTreeSet<_>>();
+            prop_assert_eq!(unique, brute_forced);
+        }
+
+        #[test]
+        fn row_major_iter_yields_count_valid_coords(size in size_up_to(Size::new(39, 39))) {
+            let coords = size.coord_iter_row_major().collect::<Vec<_>>();
+            prop_assert_eq!(coords.len(), size.count());
+            prop_assert!(coords.iter().all(|&c| c.is_valid(size)));
+            prop_assert!(coords.windows(2).all(|w| (w[0].y, w[0].x) < (w[1].y, w[1].x)));
+        }
+
+        #[test]
+        fn rotations_compose_to_identity(
+            // Small enough that rotating and negating can't overflow.
+            coord in coord_in(-(1 << 29)..(1 << 29)),
+        ) {
+            prop_assert_eq!(coord.left90().right90(), coord);
+            prop_assert_eq!(coord.right90().left90(), coord);
+            prop_assert_eq!(coord.left90().left90(), coord.opposite());
+            prop_assert_eq!(coord.left90().left90().left90().left90(), coord);
+            prop_assert_eq!(coord.opposite().opposite(), coord);
+            prop_assert_eq!(coord.transpose().transpose(), coord);
+            prop_assert_eq!(-coord, coord.opposite());
+        }
+
+        #[test]
+        fn cardinal_rotations_compose_to_identity(
+            direction in prop::sample::select(vec![
+                Coord::new(0, -1),
+                Coord::new(1, -1),
+                Coord::new(1, 0),
+                Coord::new(1, 1),
+                Coord::new(0, 1),
+                Coord::new(-1, 1),
+                Coord::new(-1, 0),
+                Coord::new(-1, -1),
+            ])
+        ) {
+            let cardinal = if direction.x == 0 || direction.y == 0 {
+                direction
+            } else {
+                direction.cardinal_left45()
+            };
+            prop_assert_eq!(cardinal.cardinal_left45().cardinal_right45(), cardinal * 2);
+            prop_assert_eq!(cardinal.cardinal_left135(), cardinal.cardinal_left45().left90());
+            prop_assert_eq!(cardinal.cardinal_right135(), cardinal.cardinal_right45().right90());
+            prop_assert_eq!(cardinal.left90().right90(), cardinal);
+        }
+
+        #[test]
+        fn magnitude2_matches_wide_arithmetic(x in -46340i32..=46340, y in -46340i32..=46340) {
+            let coord = Coord::new(x, y);
+            let expected = x as i64 * x as i64 + y as i64 * y as i64;
+            prop_assert_eq!(coord.magnitude2() as i64, expected);
+            prop_assert_eq!(coord.checked_magnitude2(), u32::try_from(expected).ok());
+        }
+
+        #[test]
+        fn checked_magnitude2_never_panics(coord in any::<Coord>(), other in any::<Coord>()) {
+            let expected = coord.x.unsigned_abs() as u64 * coord.x.unsigned_abs() as u64
+                + coord.y.unsigned_abs() as u64 * coord.y.unsigned_abs() as u64;
+            prop_assert_eq!(coord.checked_magnitude2(), u32::try_from(expected).ok());
+            let _ = coord.checked_distance2(other);
+        }
+
+        #[test]
+        fn size_arithmetic_agrees(a in any::<Size>(), b in any::<Size>(), n in any::<u32>()) {
+            prop_assert_eq!(a.checked_add(b), a.try_add(b).ok());
+            prop_assert_eq!(a.checked_sub(b), a.try_sub(b).ok());
+            prop_assert_eq!(a.checked_mul(n), a.try_mul(n).ok());
+            prop_assert_eq!(a.checked_div(n), a.try_div(n).ok());
+            prop_assert!(a.saturating_add(b).pairwise_max(a) == a.saturating_add(b));
+            let wrapped = a.wrapping_add(b);
+            prop_assert!(Size::try_new(wrapped.width(), wrapped.height()).is_ok());
+            prop_assert_eq!(
+                wrapped.width(),
+                a.width().wrapping_add(b.width()).min(MAX_SIZE_FIELD)
+            );
+            prop_assert_eq!(
+                a.wrapping_sub(b).height(),
+                a.height().wrapping_sub(b.height()).min(MAX_SIZE_FIELD)
+            );
+        }
+
+        #[test]
+        fn coord_size_arithmetic_agrees(coord in any::<Coord>(), size in any::<Size>()) {
+            prop_assert_eq!(coord.checked_add_size(size), coord.try_add_size(size).ok());
+            prop_assert_eq!(coord.checked_sub_size(size), coord.try_sub_size(size).ok());
+            prop_assert_eq!(
+                coord.wrapping_add_size(size).wrapping_sub_size(size),
+                coord
+            );
+            if let Some(sum) = coord.checked_add_size(size) {
+                prop_assert_eq!(coord + size, sum);
+                prop_assert_eq!(size + coord, sum);
+            }
+        }
+
+    }
 }
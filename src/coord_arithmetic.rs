@@ -1,171 +1,217 @@
-use crate::coord::{Coord, Size};
-use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use crate::coord::{Axis, Coord, Size, MAX_SIZE_FIELD};
+use crate::error::{ArithmeticError, ConstMessage};
+use core::iter::{Product, Sum};
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+/// Arithmetic between a `Coord` and a `Size` is done in `i64` and converted back with this,
+/// so that overflow behaves like the built-in integer operators: a panic in debug builds and
+/// wrapping in release builds.
+const fn mixed(value: i64) -> i32 {
+    if cfg!(debug_assertions) && !fits_coord_field(value) {
+        ConstMessage::new()
+            .str("arithmetic between Coord and Size overflowed: ")
+            .int(value)
+            .panic();
+    }
+    value as i32
+}
+
+/// The operators as `const fn`s, for use in constant expressions. Each behaves exactly like
+/// the corresponding operator.
+impl Coord {
+    pub const fn const_neg(self) -> Coord {
+        Coord::new(-self.x, -self.y)
+    }
+    pub const fn const_add(self, rhs: Coord) -> Coord {
+        Coord::new(self.x + rhs.x, self.y + rhs.y)
+    }
+    pub const fn const_sub(self, rhs: Coord) -> Coord {
+        Coord::new(self.x - rhs.x, self.y - rhs.y)
+    }
+    pub const fn const_add_size(self, rhs: Size) -> Coord {
+        Coord::new(
+            mixed(self.x as i64 + rhs.x() as i64),
+            mixed(self.y as i64 + rhs.y() as i64),
+        )
+    }
+    pub const fn const_sub_size(self, rhs: Size) -> Coord {
+        Coord::new(
+            mixed(self.x as i64 - rhs.x() as i64),
+            mixed(self.y as i64 - rhs.y() as i64),
+        )
+    }
+    pub const fn const_mul(self, rhs: i32) -> Coord {
+        Coord::new(self.x * rhs, self.y * rhs)
+    }
+    pub const fn const_div(self, rhs: i32) -> Coord {
+        Coord::new(self.x / rhs, self.y / rhs)
+    }
+    pub const fn const_rem(self, rhs: i32) -> Coord {
+        Coord::new(self.x % rhs, self.y % rhs)
+    }
+    /// Component-wise multiplication.
+    pub const fn const_mul_coord(self, rhs: Coord) -> Coord {
+        Coord::new(self.x * rhs.x, self.y * rhs.y)
+    }
+    /// Component-wise division.
+    pub const fn const_div_coord(self, rhs: Coord) -> Coord {
+        Coord::new(self.x / rhs.x, self.y / rhs.y)
+    }
+    /// Component-wise remainder.
+    pub const fn const_rem_coord(self, rhs: Coord) -> Coord {
+        Coord::new(self.x % rhs.x, self.y % rhs.y)
+    }
+}
+
+impl Size {
+    pub const fn const_add(self, rhs: Size) -> Size {
+        Size::new(self.x() + rhs.x(), self.y() + rhs.y())
+    }
+    pub const fn const_sub(self, rhs: Size) -> Size {
+        Size::new(self.x() - rhs.x(), self.y() - rhs.y())
+    }
+    pub const fn const_add_coord(self, rhs: Coord) -> Coord {
+        rhs.const_add_size(self)
+    }
+    pub const fn const_sub_coord(self, rhs: Coord) -> Coord {
+        Coord::new(
+            mixed(self.x() as i64 - rhs.x as i64),
+            mixed(self.y() as i64 - rhs.y as i64),
+        )
+    }
+    pub const fn const_mul(self, rhs: u32) -> Size {
+        Size::new(self.x() * rhs, self.y() * rhs)
+    }
+    pub const fn const_div(self, rhs: u32) -> Size {
+        Size::new(self.x() / rhs, self.y() / rhs)
+    }
+}
 
 impl Neg for Coord {
     type Output = Coord;
     fn neg(self) -> Self::Output {
-        Coord {
-            x: -self.x,
-            y: -self.y,
-        }
+        self.const_neg()
     }
 }
 
-impl<'a> Neg for &'a Coord {
+impl Neg for &Coord {
     type Output = Coord;
     fn neg(self) -> Self::Output {
-        Coord {
-            x: -self.x,
-            y: -self.y,
-        }
+        self.const_neg()
     }
 }
 
 impl Add for Coord {
     type Output = Coord;
-    fn add(self, Coord { x, y }: Coord) -> Self::Output {
-        Coord {
-            x: self.x + x,
-            y: self.y + y,
-        }
+    fn add(self, rhs: Coord) -> Self::Output {
+        self.const_add(rhs)
     }
 }
 
-impl<'a> Add<Coord> for &'a Coord {
+impl Add<Coord> for &Coord {
     type Output = Coord;
-    fn add(self, Coord { x, y }: Coord) -> Self::Output {
-        Coord {
-            x: self.x + x,
-            y: self.y + y,
-        }
+    fn add(self, rhs: Coord) -> Self::Output {
+        self.const_add(rhs)
     }
 }
 
-impl<'a> Add<&'a Coord> for Coord {
+impl Add<&Coord> for Coord {
     type Output = Coord;
-    fn add(self, &Coord { x, y }: &'a Coord) -> Self::Output {
-        Coord {
-            x: self.x + x,
-            y: self.y + y,
-        }
+    fn add(self, &rhs: &Coord) -> Self::Output {
+        self.const_add(rhs)
     }
 }
 
-impl<'a, 'b> Add<&'a Coord> for &'b Coord {
+impl Add<&Coord> for &Coord {
     type Output = Coord;
-    fn add(self, &Coord { x, y }: &'a Coord) -> Self::Output {
-        Coord {
-            x: self.x + x,
-            y: self.y + y,
-        }
+    fn add(self, &rhs: &Coord) -> Self::Output {
+        self.const_add(rhs)
     }
 }
 
 impl Add<Size> for Coord {
     type Output = Coord;
-    fn add(self, size: Size) -> Self::Output {
-        Coord {
-            x: self.x + size.x() as i32,
-            y: self.y + size.y() as i32,
-        }
+    fn add(self, rhs: Size) -> Self::Output {
+        self.const_add_size(rhs)
     }
 }
 
-impl<'a> Add<Size> for &'a Coord {
+impl Add<Size> for &Coord {
     type Output = Coord;
-    fn add(self, size: Size) -> Self::Output {
-        Coord {
-            x: self.x + size.x() as i32,
-            y: self.y + size.y() as i32,
-        }
+    fn add(self, rhs: Size) -> Self::Output {
+        self.const_add_size(rhs)
     }
 }
 
-impl<'a> Add<&'a Size> for Coord {
+impl Add<&Size> for Coord {
     type Output = Coord;
-    fn add(self, size: &'a Size) -> Self::Output {
-        Coord {
-            x: self.x + size.x() as i32,
-            y: self.y + size.y() as i32,
-        }
+    fn add(self, &rhs: &Size) -> Self::Output {
+        self.const_add_size(rhs)
     }
 }
 
-impl<'a, 'b> Add<&'a Size> for &'b Coord {
+impl Add<&Size> for &Coord {
     type Output = Coord;
-    fn add(self, size: &'a Size) -> Self::Output {
-        Coord {
-            x: self.x + size.x() as i32,
-            y: self.y + size.y() as i32,
-        }
+    fn add(self, &rhs: &Size) -> Self::Output {
+        self.const_add_size(rhs)
     }
 }
 
 impl Add<Coord> for Size {
     type Output = Coord;
-    fn add(self, Coord { x, y }: Coord) -> Self::Output {
-        Coord {
-            x: self.x() as i32 + x,
-            y: self.y() as i32 + y,
-        }
+    fn add(self, rhs: Coord) -> Self::Output {
+        self.const_add_coord(rhs)
     }
 }
 
-impl<'a> Add<Coord> for &'a Size {
+impl Add<Coord> for &Size {
     type Output = Coord;
-    fn add(self, Coord { x, y }: Coord) -> Self::Output {
-        Coord {
-            x: self.x() as i32 + x,
-            y: self.y() as i32 + y,
-        }
+    fn add(self, rhs: Coord) -> Self::Output {
+        self.const_add_coord(rhs)
     }
 }
 
-impl<'a> Add<&'a Coord> for Size {
+impl Add<&Coord> for Size {
     type Output = Coord;
-    fn add(self, &Coord { x, y }: &'a Coord) -> Self::Output {
-        Coord {
-            x: self.x() as i32 + x,
-            y: self.y() as i32 + y,
-        }
+    fn add(self, &rhs: &Coord) -> Self::Output {
+        self.const_add_coord(rhs)
     }
 }
 
-impl<'a, 'b> Add<&'a Coord> for &'b Size {
+impl Add<&Coord> for &Size {
     type Output = Coord;
-    fn add(self, &Coord { x, y }: &'a Coord) -> Self::Output {
-        Coord {
-            x: self.x() as i32 + x,
-            y: self.y() as i32 + y,
-        }
+    fn add(self, &rhs: &Coord) -> Self::Output {
+        self.const_add_coord(rhs)
     }
 }
 
 impl Add for Size {
     type Output = Size;
-    fn add(self, size: Size) -> Self::Output {
-        Size::new(self.x() + size.x(), self.y() + size.y())
+    fn add(self, rhs: Size) -> Self::Output {
+        self.const_add(rhs)
     }
 }
 
-impl<'a> Add<Size> for &'a Size {
+impl Add<Size> for &Size {
     type Output = Size;
-    fn add(self, size: Size) -> Self::Output {
-        Size::new(self.x() + size.x(), self.y() + size.y())
+    fn add(self, rhs: Size) -> Self::Output {
+        self.const_add(rhs)
     }
 }
 
-impl<'a> Add<&'a Size> for Size {
+impl Add<&Size> for Size {
     type Output = Size;
-    fn add(self, size: &'a Size) -> Self::Output {
-        Size::new(self.x() + size.x(), self.y() + size.y())
+    fn add(self, &rhs: &Size) -> Self::Output {
+        self.const_add(rhs)
     }
 }
 
-impl<'a, 'b> Add<&'a Size> for &'b Size {
+impl Add<&Size> for &Size {
     type Output = Size;
-    fn add(self, size: &'a Size) -> Self::Output {
-        Size::new(self.x() + size.x(), self.y() + size.y())
+    fn add(self, &rhs: &Size) -> Self::Output {
+        self.const_add(rhs)
     }
 }
 
@@ -180,149 +226,113 @@ where
 
 impl Sub for Coord {
     type Output = Coord;
-    fn sub(self, Coord { x, y }: Coord) -> Self::Output {
-        Coord {
-            x: self.x - x,
-            y: self.y - y,
-        }
+    fn sub(self, rhs: Coord) -> Self::Output {
+        self.const_sub(rhs)
     }
 }
 
-impl<'a> Sub<Coord> for &'a Coord {
+impl Sub<Coord> for &Coord {
     type Output = Coord;
-    fn sub(self, Coord { x, y }: Coord) -> Self::Output {
-        Coord {
-            x: self.x - x,
-            y: self.y - y,
-        }
+    fn sub(self, rhs: Coord) -> Self::Output {
+        self.const_sub(rhs)
     }
 }
 
-impl<'a> Sub<&'a Coord> for Coord {
+impl Sub<&Coord> for Coord {
     type Output = Coord;
-    fn sub(self, &Coord { x, y }: &'a Coord) -> Self::Output {
-        Coord {
-            x: self.x - x,
-            y: self.y - y,
-        }
+    fn sub(self, &rhs: &Coord) -> Self::Output {
+        self.const_sub(rhs)
     }
 }
 
-impl<'a, 'b> Sub<&'a Coord> for &'b Coord {
+impl Sub<&Coord> for &Coord {
     type Output = Coord;
-    fn sub(self, &Coord { x, y }: &'a Coord) -> Self::Output {
-        Coord {
-            x: self.x - x,
-            y: self.y - y,
-        }
+    fn sub(self, &rhs: &Coord) -> Self::Output {
+        self.const_sub(rhs)
     }
 }
 
 impl Sub<Size> for Coord {
     type Output = Coord;
-    fn sub(self, size: Size) -> Self::Output {
-        Coord {
-            x: self.x - size.x() as i32,
-            y: self.y - size.y() as i32,
-        }
+    fn sub(self, rhs: Size) -> Self::Output {
+        self.const_sub_size(rhs)
     }
 }
 
-impl<'a> Sub<Size> for &'a Coord {
+impl Sub<Size> for &Coord {
     type Output = Coord;
-    fn sub(self, size: Size) -> Self::Output {
-        Coord {
-            x: self.x - size.x() as i32,
-            y: self.y - size.y() as i32,
-        }
+    fn sub(self, rhs: Size) -> Self::Output {
+        self.const_sub_size(rhs)
     }
 }
 
-impl<'a> Sub<&'a Size> for Coord {
+impl Sub<&Size> for Coord {
     type Output = Coord;
-    fn sub(self, size: &'a Size) -> Self::Output {
-        Coord {
-            x: self.x - size.x() as i32,
-            y: self.y - size.y() as i32,
-        }
+    fn sub(self, &rhs: &Size) -> Self::Output {
+        self.const_sub_size(rhs)
     }
 }
 
-impl<'a, 'b> Sub<&'a Size> for &'b Coord {
+impl Sub<&Size> for &Coord {
     type Output = Coord;
-    fn sub(self, size: &'a Size) -> Self::Output {
-        Coord {
-            x: self.x - size.x() as i32,
-            y: self.y - size.y() as i32,
-        }
+    fn sub(self, &rhs: &Size) -> Self::Output {
+        self.const_sub_size(rhs)
     }
 }
 
 impl Sub<Coord> for Size {
     type Output = Coord;
-    fn sub(self, Coord { x, y }: Coord) -> Self::Output {
-        Coord {
-            x: self.x() as i32 - x,
-            y: self.y() as i32 - y,
-        }
+    fn sub(self, rhs: Coord) -> Self::Output {
+        self.const_sub_coord(rhs)
     }
 }
 
-impl<'a> Sub<Coord> for &'a Size {
+impl Sub<Coord> for &Size {
     type Output = Coord;
-    fn sub(self, Coord { x, y }: Coord) -> Self::Output {
-        Coord {
-            x: self.x() as i32 - x,
-            y: self.y() as i32 - y,
-        }
+    fn sub(self, rhs: Coord) -> Self::Output {
+        self.const_sub_coord(rhs)
     }
 }
 
-impl<'a> Sub<&'a Coord> for Size {
+impl Sub<&Coord> for Size {
     type Output = Coord;
-    fn sub(self, &Coord { x, y }: &'a Coord) -> Self::Output {
-        Coord {
-            x: self.x() as i32 - x,
-            y: self.y() as i32 - y,
-        }
+    fn sub(self, &rhs: &Coord) -> Self::Output {
+        self.const_sub_coord(rhs)
     }
 }
 
-impl<'a, 'b> Sub<&'a Coord> for &'b Size {
+impl Sub<&Coord> for &Size {
     type Output = Coord;
-    fn sub(self, &Coord { x, y }: &'a Coord) -> Self::Output {
-        Coord {
-            x: self.x() as i32 - x,
-            y: self.y() as i32 - y,
-        }
+    fn sub(self, &rhs: &Coord) -> Self::Output {
+        self.const_sub_coord(rhs)
     }
 }
 
 impl Sub for Size {
     type Output = Size;
-    fn sub(self, size: Size) -> Self::Output {
-        Size::new(self.x() - size.x(), self.y() - size.y())
+    fn sub(self, rhs: Size) -> Self::Output {
+        self.const_sub(rhs)
     }
 }
 
-impl<'a> Sub<Size> for &'a Size {
+impl Sub<Size> for &Size {
     type Output = Size;
-    fn sub(self, size: Size) -> Self::Output {
-        Size::new(self.x() - size.x(), self.y() - size.y())
+    fn sub(self, rhs: Size) -> Self::Output {
+        self.const_sub(rhs)
     }
 }
 
-impl<'a> Sub<&'a Size> for Size {
+impl Sub<&Size> for Size {
     type Output = Size;
-    fn sub(self, size: &'a Size) -> Self::Output {
-        Size::new(self.x() - size.x(), self.y() - size.y())
+    fn sub(self, &rhs: &Size) -> Self::Output {
+        self.const_sub(rhs)
     }
 }
 
-impl<'a, 'b> Sub<&'a Size> for &'b Size {
+impl Sub<&Size> for &Size {
     type Output = Size;
-    fn sub(self, size: &'a Size) -> Self::Output {
-        Size::new(self.x() - size.x(), self.y() - size.y())
+    fn sub(self, &rhs: &Size) -> Self::Output {
+        self.const_sub(rhs)
     }
 }
 
@@ -338,20 +348,14 @@ where
 impl Mul<i32> for Coord {
     type Output = Coord;
     fn mul(self, rhs: i32) -> Self::Output {
-        Coord {
-            x: self.x * rhs,
-            y: self.y * rhs,
-        }
+        self.const_mul(rhs)
     }
 }
 
-impl<'a> Mul<i32> for &'a Coord {
+impl Mul<i32> for &Coord {
     type Output = Coord;
     fn mul(self, rhs: i32) -> Self::Output {
-        Coord {
-            x: self.x * rhs,
-            y: self.y * rhs,
-        }
+        self.const_mul(rhs)
     }
 }
 
@@ -364,17 +368,31 @@ where
     }
 }
 
+impl Mul for Coord {
+    type Output = Coord;
+    fn mul(self, rhs: Coord) -> Self::Output {
+        self.const_mul_coord(rhs)
+    }
+}
+
+impl Mul<Coord> for &Coord {
+    type Output = Coord;
+    fn mul(self, rhs: Coord) -> Self::Output {
+        self.const_mul_coord(rhs)
+    }
+}
+
 impl Mul<u32> for Size {
     type Output = Size;
     fn mul(self, rhs: u32) -> Self::Output {
-        Size::new(self.x() * rhs, self.y() * rhs)
+        self.const_mul(rhs)
     }
 }
 
-impl<'a> Mul<u32> for &'a Size {
+impl Mul<u32> for &Size {
     type Output = Size;
     fn mul(self, rhs: u32) -> Self::Output {
-        Size::new(self.x() * rhs, self.y() * rhs)
+        self.const_mul(rhs)
     }
 }
 
@@ -390,20 +408,14 @@ where
 impl Div<i32> for Coord {
     type Output = Coord;
     fn div(self, rhs: i32) -> Self::Output {
-        Coord {
-            x: self.x / rhs,
-            y: self.y / rhs,
-        }
+        self.const_div(rhs)
     }
 }
 
-impl<'a> Div<i32> for &'a Coord {
+impl Div<i32> for &Coord {
     type Output = Coord;
     fn div(self, rhs: i32) -> Self::Output {
-        Coord {
-            x: self.x / rhs,
-            y: self.y / rhs,
-        }
+        self.const_div(rhs)
     }
 }
 
@@ -416,17 +428,31 @@ where
     }
 }
 
+impl Div for Coord {
+    type Output = Coord;
+    fn div(self, rhs: Coord) -> Self::Output {
+        self.const_div_coord(rhs)
+    }
+}
+
+impl Div<Coord> for &Coord {
+    type Output = Coord;
+    fn div(self, rhs: Coord) -> Self::Output {
+        self.const_div_coord(rhs)
+    }
+}
+
 impl Div<u32> for Size {
     type Output = Size;
     fn div(self, rhs: u32) -> Self::Output {
-        Size::new(self.x() / rhs, self.y() / rhs)
+        self.const_div(rhs)
     }
 }
 
-impl<'a> Div<u32> for &'a Size {
+impl Div<u32> for &Size {
     type Output = Size;
     fn div(self, rhs: u32) -> Self::Output {
-        Size::new(self.x() / rhs, self.y() / rhs)
+        self.const_div(rhs)
     }
 }
 
@@ -439,11 +465,231 @@ where
     }
 }
 
+impl Rem<i32> for Coord {
+    type Output = Coord;
+    fn rem(self, rhs: i32) -> Self::Output {
+        self.const_rem(rhs)
+    }
+}
+
+impl Rem<i32> for &Coord {
+    type Output = Coord;
+    fn rem(self, rhs: i32) -> Self::Output {
+        self.const_rem(rhs)
+    }
+}
+
+impl Rem for Coord {
+    type Output = Coord;
+    fn rem(self, rhs: Coord) -> Self::Output {
+        self.const_rem_coord(rhs)
+    }
+}
+
+impl Rem<Coord> for &Coord {
+    type Output = Coord;
+    fn rem(self, rhs: Coord) -> Self::Output {
+        self.const_rem_coord(rhs)
+    }
+}
+
+impl<T> RemAssign<T> for Coord
+where
+    Coord: Rem<T, Output = Coord>,
+{
+    fn rem_assign(&mut self, rhs: T) {
+        *self = *self % rhs;
+    }
+}
+
+impl Sum for Coord {
+    fn sum<I: Iterator<Item = Coord>>(iter: I) -> Self {
+        iter.fold(Coord::new(0, 0), |acc, coord| acc + coord)
+    }
+}
+
+impl<'a> Sum<&'a Coord> for Coord {
+    fn sum<I: Iterator<Item = &'a Coord>>(iter: I) -> Self {
+        iter.fold(Coord::new(0, 0), |acc, coord| acc + coord)
+    }
+}
+
+impl Product for Coord {
+    fn product<I: Iterator<Item = Coord>>(iter: I) -> Self {
+        iter.fold(Coord::new(1, 1), |acc, coord| acc * coord)
+    }
+}
+
+impl<'a> Product<&'a Coord> for Coord {
+    fn product<I: Iterator<Item = &'a Coord>>(iter: I) -> Self {
+        iter.fold(Coord::new(1, 1), |acc, &coord| acc * coord)
+    }
+}
+
+impl Sum for Size {
+    fn sum<I: Iterator<Item = Size>>(iter: I) -> Self {
+        iter.fold(Size::new_u16(0, 0), |acc, size| acc + size)
+    }
+}
+
+impl<'a> Sum<&'a Size> for Size {
+    fn sum<I: Iterator<Item = &'a Size>>(iter: I) -> Self {
+        iter.fold(Size::new_u16(0, 0), |acc, size| acc + size)
+    }
+}
+
+/// Component-wise product. Panics if the result would be too large for a `Size`.
+impl Product for Size {
+    fn product<I: Iterator<Item = Size>>(iter: I) -> Self {
+        iter.fold(Size::new_u16(1, 1), |acc, size| {
+            let field = |acc: u32, value: u32, axis| {
+                // Both factors are at most `MAX_SIZE_FIELD` so the product fits in an `i64`.
+                match try_size_field(acc as i64 * value as i64, axis) {
+                    Ok(field) => field,
+                    Err(e) => panic!("{}", e),
+                }
+            };
+            Size::new(
+                field(acc.x(), size.x(), Axis::X),
+                field(acc.y(), size.y(), Axis::Y),
+            )
+        })
+    }
+}
+
+impl<'a> Product<&'a Size> for Size {
+    fn product<I: Iterator<Item = &'a Size>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+const fn fits_coord_field(value: i64) -> bool {
+    value >= i32::MIN as i64 && value <= i32::MAX as i64
+}
+
+fn try_size_field(value: i64, axis: Axis) -> Result<u32, ArithmeticError> {
+    if value < 0 || value > MAX_SIZE_FIELD as i64 {
+        Err(ArithmeticError::SizeOutOfRange { axis, value })
+    } else {
+        Ok(value as u32)
+    }
+}
+
+/// The error for a `Coord` with the given components, the first of which is out of range.
+const fn coord_out_of_range(x: i64, y: i64) -> ArithmeticError {
+    if !fits_coord_field(x) {
+        ArithmeticError::CoordOutOfRange {
+            axis: Axis::X,
+            value: x,
+        }
+    } else {
+        ArithmeticError::CoordOutOfRange {
+            axis: Axis::Y,
+            value: y,
+        }
+    }
+}
+
+/// The error for a `Size` with the given dimensions, the first of which is out of range.
+const fn size_out_of_range(x: i64, y: i64) -> ArithmeticError {
+    if x < 0 || x > MAX_SIZE_FIELD as i64 {
+        ArithmeticError::SizeOutOfRange {
+            axis: Axis::X,
+            value: x,
+        }
+    } else {
+        ArithmeticError::SizeOutOfRange {
+            axis: Axis::Y,
+            value: y,
+        }
+    }
+}
+
+/// Non-panicking forms of the arithmetic operators, which report which component went out of
+/// range rather than panicking or wrapping. These are the `checked_*` operations with the
+/// offending component added to the error.
+impl Coord {
+    pub const fn try_add_size(self, size: Size) -> Result<Coord, ArithmeticError> {
+        match self.checked_add_size(size) {
+            Some(coord) => Ok(coord),
+            None => Err(coord_out_of_range(
+                self.x as i64 + size.x() as i64,
+                self.y as i64 + size.y() as i64,
+            )),
+        }
+    }
+
+    pub const fn try_sub_size(self, size: Size) -> Result<Coord, ArithmeticError> {
+        match self.checked_sub_size(size) {
+            Some(coord) => Ok(coord),
+            None => Err(coord_out_of_range(
+                self.x as i64 - size.x() as i64,
+                self.y as i64 - size.y() as i64,
+            )),
+        }
+    }
+}
+
+impl Size {
+    pub const fn try_add_coord(self, coord: Coord) -> Result<Coord, ArithmeticError> {
+        coord.try_add_size(self)
+    }
+
+    pub const fn try_sub_coord(self, coord: Coord) -> Result<Coord, ArithmeticError> {
+        let x = self.x() as i64 - coord.x as i64;
+        let y = self.y() as i64 - coord.y as i64;
+        if fits_coord_field(x) && fits_coord_field(y) {
+            Ok(Coord::new(x as i32, y as i32))
+        } else {
+            Err(coord_out_of_range(x, y))
+        }
+    }
+
+    pub const fn try_add(self, size: Size) -> Result<Size, ArithmeticError> {
+        match self.checked_add(size) {
+            Some(size) => Ok(size),
+            None => Err(size_out_of_range(
+                self.x() as i64 + size.x() as i64,
+                self.y() as i64 + size.y() as i64,
+            )),
+        }
+    }
+
+    pub const fn try_sub(self, size: Size) -> Result<Size, ArithmeticError> {
+        match self.checked_sub(size) {
+            Some(size) => Ok(size),
+            None => Err(size_out_of_range(
+                self.x() as i64 - size.x() as i64,
+                self.y() as i64 - size.y() as i64,
+            )),
+        }
+    }
+
+    pub const fn try_mul(self, rhs: u32) -> Result<Size, ArithmeticError> {
+        match self.checked_mul(rhs) {
+            Some(size) => Ok(size),
+            None => Err(size_out_of_range(
+                self.x() as i64 * rhs as i64,
+                self.y() as i64 * rhs as i64,
+            )),
+        }
+    }
+
+    pub const fn try_div(self, rhs: u32) -> Result<Size, ArithmeticError> {
+        match self.checked_div(rhs) {
+            Some(size) => Ok(size),
+            None => Err(ArithmeticError::DivisionByZero),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::coord::{Coord, Size};
+    use crate::coord::{Axis, Coord, Size, MAX_SIZE_FIELD};
+    use crate::error::ArithmeticError;
 
     #[test]
+    #[allow(clippy::op_ref, clippy::let_unit_value)]
     fn arithmetic() {
         let mut a = Coord::new(0, 0);
         let _ = a + Coord::new(0, 0);
@@ -463,4 +709,139 @@ mod test {
         let _ = a += &Size::new(0, 0);
         let _ = a += &Coord::new(0, 0);
     }
+
+    #[test]
+    fn size_overflow() {
+        let a = Coord::new(-5, -1);
+        assert_eq!(a + Size::max(), Coord::new(i32::MAX - 4, i32::MAX));
+        assert_eq!(
+            Size::max() - Coord::new(1, 1),
+            Coord::new(i32::MAX, i32::MAX)
+        );
+        assert_eq!(Coord::new(0, -1).checked_add_size(Size::max()), None);
+        assert_eq!(
+            Coord::new(0, -1).saturating_add_size(Size::max()),
+            Coord::new(i32::MAX, i32::MAX)
+        );
+        assert_eq!(Size::max().checked_add(Size::new(0, 1)), None);
+        assert_eq!(Size::max().saturating_add(Size::new(1, 0)), Size::max());
+    }
+
+    #[test]
+    fn size_wrapping() {
+        // Wrapping is at 2^32, like `u32`, and dimensions beyond `MAX_SIZE_FIELD` are reduced
+        // to it.
+        assert_eq!(Size::max().wrapping_add(Size::max()), Size::new(0, 0));
+        assert_eq!(Size::max().wrapping_add(Size::new(1, 0)), Size::max());
+        assert_eq!(
+            Size::new(MAX_SIZE_FIELD - 1, 5).wrapping_add(Size::new(1, 5)),
+            Size::new(MAX_SIZE_FIELD, 10)
+        );
+        assert_eq!(
+            Size::new(0, 3).wrapping_sub(Size::new(1, 1)),
+            Size::new(MAX_SIZE_FIELD, 2)
+        );
+        assert_eq!(
+            Size::new(2, 3).wrapping_sub(Size::max()),
+            Size::new(MAX_SIZE_FIELD, MAX_SIZE_FIELD)
+        );
+        assert_eq!(
+            Size::new(1 << 16, 3).wrapping_mul(1 << 16),
+            Size::new(0, 3 << 16)
+        );
+        assert_eq!(Size::max().wrapping_mul(3), Size::max());
+        assert_eq!(Size::new(5, 7).wrapping_mul(0), Size::new(0, 0));
+    }
+
+    #[test]
+    fn size_product() {
+        let sizes = [Size::new(1 << 16, 2), Size::new(1 << 15, 3)];
+        assert_eq!(sizes.iter().product::<Size>(), Size::new(MAX_SIZE_FIELD, 6));
+    }
+
+    #[test]
+    #[should_panic(expected = "resulting width 4294967296 is out of range")]
+    fn size_product_overflow() {
+        let _: Size = [Size::new(1 << 16, 1), Size::new(1 << 16, 1)]
+            .into_iter()
+            .product();
+    }
+
+    #[test]
+    fn fallible() {
+        assert_eq!(
+            Coord::new(1, -1).try_add_size(Size::max()),
+            Err(ArithmeticError::CoordOutOfRange {
+                axis: Axis::X,
+                value: MAX_SIZE_FIELD as i64 + 1,
+            })
+        );
+        assert_eq!(
+            Size::new(2, 3).try_sub_coord(Coord::new(0, i32::MIN)),
+            Err(ArithmeticError::CoordOutOfRange {
+                axis: Axis::Y,
+                value: 3 + MAX_SIZE_FIELD as i64,
+            })
+        );
+        assert_eq!(
+            Size::new(1, 2).try_sub(Size::new(1, 3)),
+            Err(ArithmeticError::SizeOutOfRange {
+                axis: Axis::Y,
+                value: -1,
+            })
+        );
+        assert_eq!(
+            Size::new(u16::MAX as u32, 0).try_mul(u16::MAX as u32),
+            Err(ArithmeticError::SizeOutOfRange {
+                axis: Axis::X,
+                value: u16::MAX as i64 * u16::MAX as i64,
+            })
+        );
+        assert_eq!(
+            Size::new(1, 1).try_div(0),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(Size::new(9, 4).try_div(2), Ok(Size::new(4, 2)));
+    }
+
+    #[test]
+    fn try_arithmetic_const() {
+        const ORIGIN: Result<Coord, ArithmeticError> =
+            Size::new(3, 4).try_sub_coord(Coord::new(3, 4));
+        const TOO_FAR: Result<Coord, ArithmeticError> =
+            Size::max().try_sub_coord(Coord::new(0, -1));
+        const HALF: Result<Size, ArithmeticError> = Size::new(9, 4).try_div(2);
+        assert_eq!(ORIGIN, Ok(Coord::new(0, 0)));
+        assert_eq!(
+            TOO_FAR,
+            Err(ArithmeticError::CoordOutOfRange {
+                axis: Axis::X,
+                value: MAX_SIZE_FIELD as i64,
+            })
+        );
+        assert_eq!(HALF, Ok(Size::new(4, 2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "arithmetic between Coord and Size overflowed: 2147483653")]
+    #[cfg(debug_assertions)]
+    fn size_overflow_panics() {
+        let _ = Coord::new(5, 0) + Size::max();
+    }
+
+    #[test]
+    fn component_wise() {
+        let a = Coord::new(-7, 9);
+        assert_eq!(a * Coord::new(2, -1), Coord::new(-14, -9));
+        assert_eq!(a / Coord::new(2, 4), Coord::new(-3, 2));
+        assert_eq!(a % Coord::new(2, 4), Coord::new(-1, 1));
+        assert_eq!(a.div_euclid(Coord::new(2, 4)), Coord::new(-4, 2));
+        assert_eq!(a.rem_euclid(Coord::new(2, 4)), Coord::new(1, 1));
+        let coords = [Coord::new(1, 2), Coord::new(3, 4)];
+        assert_eq!(coords.iter().sum::<Coord>(), Coord::new(4, 6));
+        assert_eq!(coords.iter().product::<Coord>(), Coord::new(3, 8));
+        let sizes = [Size::new(1, 2), Size::new(3, 4)];
+        assert_eq!(sizes.iter().sum::<Size>(), Size::new(4, 6));
+        assert_eq!(sizes.iter().product::<Size>(), Size::new(3, 8));
+    }
 }
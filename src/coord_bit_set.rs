@@ -0,0 +1,401 @@
+//! A dense set of the coordinates within a `Size`, stored as one bit per cell.
+
+use crate::coord::{Axis, Coord, Size};
+use crate::span::Span;
+use alloc::{vec, vec::Vec};
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, Sub, SubAssign,
+};
+
+const WORD_BITS: u32 = u64::BITS;
+
+/// A set of the coordinates within a `Size`, with one bit per cell.
+///
+/// Each row is stored in its own run of `u64` words, with the cell at `x` in bit `x % 64` of
+/// word `x / 64`, so rows can be manipulated a word at a time. Bits past the end of a row are
+/// always clear.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CoordBitSet {
+    size: Size,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl CoordBitSet {
+    /// An empty set of coordinates within `size`.
+    pub fn new(size: Size) -> Self {
+        let words_per_row = size.width().div_ceil(WORD_BITS) as usize;
+        Self {
+            size,
+            words_per_row,
+            words: vec![0; words_per_row * size.height() as usize],
+        }
+    }
+
+    /// The set of every coordinate within `size`.
+    pub fn full(size: Size) -> Self {
+        let mut set = Self::new(size);
+        set.fill(true);
+        set
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// The mask of the bits of the last word of each row which are within the row.
+    fn last_word_mask(&self) -> u64 {
+        match self.size.width() % WORD_BITS {
+            0 => !0,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    fn bit(&self, coord: Coord) -> Option<(usize, u64)> {
+        if !coord.is_valid(self.size) {
+            return None;
+        }
+        let x = coord.x as usize;
+        let index = coord.y as usize * self.words_per_row + x / WORD_BITS as usize;
+        Some((index, 1 << (x % WORD_BITS as usize)))
+    }
+
+    /// Returns false if `coord` is outside the size of the set.
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.bit(coord)
+            .is_some_and(|(index, mask)| self.words[index] & mask != 0)
+    }
+
+    /// Returns true if `coord` was not already in the set.
+    /// Panics if `coord` is outside the size of the set.
+    pub fn insert(&mut self, coord: Coord) -> bool {
+        let (index, mask) = self.expect_bit(coord);
+        let inserted = self.words[index] & mask == 0;
+        self.words[index] |= mask;
+        inserted
+    }
+
+    /// Returns true if `coord` was in the set. Coordinates outside the size of the set are
+    /// never in it.
+    pub fn remove(&mut self, coord: Coord) -> bool {
+        match self.bit(coord) {
+            Some((index, mask)) => {
+                let removed = self.words[index] & mask != 0;
+                self.words[index] &= !mask;
+                removed
+            }
+            None => false,
+        }
+    }
+
+    /// Panics if `coord` is outside the size of the set.
+    pub fn set(&mut self, coord: Coord, value: bool) {
+        if value {
+            self.insert(coord);
+        } else {
+            self.remove(coord);
+        }
+    }
+
+    /// Inverts the membership of `coord`, returning true if it's now in the set.
+    /// Panics if `coord` is outside the size of the set.
+    pub fn toggle(&mut self, coord: Coord) -> bool {
+        let (index, mask) = self.expect_bit(coord);
+        self.words[index] ^= mask;
+        self.words[index] & mask != 0
+    }
+
+    fn expect_bit(&self, coord: Coord) -> (usize, u64) {
+        match self.bit(coord) {
+            Some(bit) => bit,
+            None => panic!("{:?} is outside the size {:?}", coord, self.size),
+        }
+    }
+
+    /// The number of coordinates in the set.
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns true if no coordinates are in the set.
+    pub fn is_clear(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.fill(false);
+    }
+
+    /// Adds every coordinate to the set if `value` is true, or removes every coordinate if
+    /// it's false.
+    pub fn fill(&mut self, value: bool) {
+        self.words.fill(if value { !0 } else { 0 });
+        if value {
+            self.clear_padding();
+        }
+    }
+
+    fn clear_padding(&mut self) {
+        if self.words_per_row == 0 {
+            return;
+        }
+        let mask = self.last_word_mask();
+        for row in self.words.chunks_exact_mut(self.words_per_row) {
+            *row.last_mut().unwrap() &= mask;
+        }
+    }
+
+    /// Replaces the set with its complement within its size.
+    pub fn invert(&mut self) {
+        for word in &mut self.words {
+            *word = !*word;
+        }
+        self.clear_padding();
+    }
+
+    /// Iterates over the coordinates in the set in row-major order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            set: self,
+            index: 0,
+            word: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    /// The words storing row `y`. Panics if `y` is outside the size of the set.
+    pub fn row_words(&self, y: u32) -> &[u64] {
+        assert!(y < self.size.height(), "row {} is out of bounds", y);
+        let start = y as usize * self.words_per_row;
+        &self.words[start..start + self.words_per_row]
+    }
+
+    /// Replaces word `index` of row `y`, which stores the cells from `x = index * 64`. Bits
+    /// for cells beyond the width of the set are ignored. Panics if the word is out of bounds.
+    pub fn set_row_word(&mut self, y: u32, index: usize, word: u64) {
+        assert!(y < self.size.height(), "row {} is out of bounds", y);
+        assert!(
+            index < self.words_per_row,
+            "word {} is out of bounds",
+            index
+        );
+        let mask = if index == self.words_per_row - 1 {
+            self.last_word_mask()
+        } else {
+            !0
+        };
+        self.words[y as usize * self.words_per_row + index] = word & mask;
+    }
+
+    /// Adds (if `value` is true) or removes (if it's false) the cells of row `y` whose x
+    /// coordinates are in `span`, a word at a time. Parts of `span` outside the set are
+    /// ignored. Panics if `y` is outside the size of the set.
+    pub fn set_row_span(&mut self, y: u32, span: Span, value: bool) {
+        assert!(y < self.size.height(), "row {} is out of bounds", y);
+        let span = span.intersection(Span::of_size(self.size, Axis::X));
+        if span.is_empty() {
+            return;
+        }
+        let (start, end) = (span.start as usize, span.end as usize);
+        let row_start = y as usize * self.words_per_row;
+        let bits = WORD_BITS as usize;
+        for index in start / bits..end.div_ceil(bits) {
+            let word_start = index * bits;
+            let low = start.saturating_sub(word_start);
+            let high = (end - word_start).min(bits);
+            let mask = (!0u64 >> (bits - (high - low))) << low;
+            let word = &mut self.words[row_start + index];
+            if value {
+                *word |= mask;
+            } else {
+                *word &= !mask;
+            }
+        }
+    }
+
+    fn assert_same_size(&self, other: &Self) {
+        assert_eq!(self.size, other.size, "bit sets have different sizes");
+    }
+
+    fn combine_with(&mut self, other: &Self, f: impl Fn(u64, u64) -> u64) {
+        self.assert_same_size(other);
+        for (word, &other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word = f(*word, other);
+        }
+    }
+
+    /// Panics if the sets have different sizes.
+    pub fn union_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a | b)
+    }
+
+    /// Panics if the sets have different sizes.
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a & b)
+    }
+
+    /// Panics if the sets have different sizes.
+    pub fn difference_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a & !b)
+    }
+
+    /// Panics if the sets have different sizes.
+    pub fn symmetric_difference_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a ^ b)
+    }
+
+    /// Panics if the sets have different sizes.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.assert_same_size(other);
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .all(|(&a, &b)| a & !b == 0)
+    }
+}
+
+/// Panics if `coord` is outside the size of the set, like indexing a slice.
+impl Index<Coord> for CoordBitSet {
+    type Output = bool;
+    fn index(&self, coord: Coord) -> &bool {
+        let (index, mask) = self.expect_bit(coord);
+        if self.words[index] & mask != 0 {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+macro_rules! set_operator {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $with:ident) => {
+        /// Panics if the sets have different sizes.
+        impl $assign_trait<&CoordBitSet> for CoordBitSet {
+            fn $assign_method(&mut self, rhs: &CoordBitSet) {
+                self.$with(rhs);
+            }
+        }
+
+        /// Panics if the sets have different sizes.
+        impl $trait for &CoordBitSet {
+            type Output = CoordBitSet;
+            fn $method(self, rhs: &CoordBitSet) -> CoordBitSet {
+                let mut set = self.clone();
+                set.$with(rhs);
+                set
+            }
+        }
+    };
+}
+
+set_operator!(BitOr, bitor, BitOrAssign, bitor_assign, union_with);
+set_operator!(BitAnd, bitand, BitAndAssign, bitand_assign, intersect_with);
+set_operator!(Sub, sub, SubAssign, sub_assign, difference_with);
+set_operator!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    symmetric_difference_with
+);
+
+impl<'a> IntoIterator for &'a CoordBitSet {
+    type Item = Coord;
+    type IntoIter = Iter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the coordinates in a `CoordBitSet`, returned by `CoordBitSet::iter`.
+#[derive(Debug, Clone)]
+pub struct Iter<'a> {
+    set: &'a CoordBitSet,
+    index: usize,
+    /// The bits of the word at `index` which are yet to be yielded.
+    word: u64,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Coord;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.word == 0 {
+            self.index += 1;
+            self.word = *self.set.words.get(self.index)?;
+        }
+        let bit = self.word.trailing_zeros();
+        self.word &= self.word - 1;
+        let y = self.index / self.set.words_per_row;
+        let x = (self.index % self.set.words_per_row) * WORD_BITS as usize + bit as usize;
+        Some(Coord::new(x as i32, y as i32))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CoordBitSet;
+    use crate::coord::{Coord, Size};
+    use crate::span::Span;
+
+    #[test]
+    fn membership() {
+        let mut set = CoordBitSet::new(Size::new(70, 3));
+        assert!(set.is_clear());
+        assert!(set.insert(Coord::new(69, 2)));
+        assert!(!set.insert(Coord::new(69, 2)));
+        assert!(set[Coord::new(69, 2)]);
+        assert!(!set[Coord::new(68, 2)]);
+        assert!(!set.contains(Coord::new(70, 2)));
+        assert!(set.toggle(Coord::new(0, 0)));
+        assert_eq!(set.count(), 2);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [Coord::new(0, 0), Coord::new(69, 2)]
+        );
+        assert!(set.remove(Coord::new(0, 0)));
+        assert!(!set.remove(Coord::new(-1, 0)));
+        set.invert();
+        assert_eq!(set.count(), 70 * 3 - 1);
+        assert_eq!(CoordBitSet::full(Size::new(70, 3)).count(), 210);
+        assert_eq!(CoordBitSet::full(Size::new(0, 3)).iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        CoordBitSet::new(Size::new(2, 2)).insert(Coord::new(2, 0));
+    }
+
+    #[test]
+    fn algebra() {
+        let size = Size::new(10, 10);
+        let mut a = CoordBitSet::new(size);
+        let mut b = CoordBitSet::new(size);
+        for i in 0..10 {
+            a.insert(Coord::new(i, i));
+            b.insert(Coord::new(i, 0));
+        }
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), [Coord::new(0, 0)]);
+        assert_eq!((&a | &b).count(), 19);
+        assert_eq!((&a ^ &b).count(), 18);
+        assert_eq!((&a - &b).count(), 9);
+        assert!((&a & &b).is_subset(&a));
+        a -= &b;
+        assert!(!a.contains(Coord::new(0, 0)));
+    }
+
+    #[test]
+    fn rows() {
+        let mut set = CoordBitSet::new(Size::new(130, 2));
+        set.set_row_span(1, Span::new(60, 200), true);
+        assert_eq!(set.count(), 70);
+        assert_eq!(set.row_words(1), [0xF << 60, !0, 0b11]);
+        set.set_row_span(1, Span::new(62, 129), false);
+        assert_eq!(set.iter().collect::<Vec<_>>().len(), 3);
+        set.set_row_word(0, 2, !0);
+        assert_eq!(set.row_words(0)[2], 0b11);
+        assert_eq!(set.count(), 5);
+    }
+}
@@ -0,0 +1,580 @@
+//! Sparse collections keyed by `Coord`.
+//!
+//! Entries are stored in vectors sorted by the Morton code (Z-order) of their keys, which
+//! interleaves the bits of the two components. Cells which are close together in 2D tend to
+//! be close together in this order, so a rectangular region query only needs to visit a few
+//! runs of entries. Lookups are a binary search. Inserting or removing a single entry moves
+//! the entries after it, so these collections suit workloads with more queries than updates;
+//! building one from an iterator sorts the entries once.
+
+use crate::coord::{Coord, Size};
+use crate::neighbourhood::Neighbourhood;
+use alloc::vec::Vec;
+use core::{cmp::Ordering, fmt, iter, slice};
+
+/// Maps an `i32` to a `u32` with the same ordering.
+const fn bias(value: i32) -> u32 {
+    (value as u32) ^ 0x8000_0000
+}
+
+const fn unbias(value: u32) -> i32 {
+    (value ^ 0x8000_0000) as i32
+}
+
+/// Moves bit `i` of `value` to bit `2 * i` of the result.
+const fn spread(value: u32) -> u64 {
+    let mut v = value as u64;
+    v = (v | (v << 16)) & 0x0000_FFFF_0000_FFFF;
+    v = (v | (v << 8)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    (v | (v << 1)) & EVEN_BITS
+}
+
+/// The inverse of `spread`, ignoring odd bits.
+const fn compact(value: u64) -> u32 {
+    let mut v = value & EVEN_BITS;
+    v = (v | (v >> 1)) & 0x3333_3333_3333_3333;
+    v = (v | (v >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v >> 4)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v >> 8)) & 0x0000_FFFF_0000_FFFF;
+    ((v | (v >> 16)) & 0x0000_0000_FFFF_FFFF) as u32
+}
+
+const EVEN_BITS: u64 = 0x5555_5555_5555_5555;
+
+/// The Morton code of a coordinate, with x in the even bits and y in the odd bits.
+const fn encode(coord: Coord) -> u64 {
+    spread(bias(coord.x)) | (spread(bias(coord.y)) << 1)
+}
+
+const fn decode(code: u64) -> Coord {
+    Coord::new(unbias(compact(code)), unbias(compact(code >> 1)))
+}
+
+/// The smallest Morton code greater than `code` which is inside the box whose minimum and
+/// maximum corners have the codes `min` and `max`, where `code` is between `min` and `max` but
+/// outside the box. This is the BIGMIN calculation of Tropf and Herzog.
+fn next_in_box(code: u64, mut min: u64, mut max: u64) -> Option<u64> {
+    let mut next = None;
+    for bit in (0..64).rev() {
+        let mask = 1u64 << bit;
+        // The lower bits belonging to the same component as this bit.
+        let below = (mask - 1) & (EVEN_BITS << (bit % 2));
+        // Sets this bit and clears the lower bits of the same component.
+        let load_min = |value: u64| (value | mask) & !below;
+        // Clears this bit and sets the lower bits of the same component.
+        let load_max = |value: u64| (value & !mask) | below;
+        match (code & mask != 0, min & mask != 0, max & mask != 0) {
+            (false, false, true) => {
+                next = Some(load_min(min));
+                max = load_max(max);
+            }
+            (false, true, true) => return Some(min),
+            (true, false, false) => return next,
+            (true, false, true) => min = load_min(min),
+            _ => (),
+        }
+    }
+    next
+}
+
+/// A map from `Coord` to `T` supporting efficient rectangular region queries.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CoordMap<T> {
+    keys: Vec<u64>,
+    values: Vec<T>,
+}
+
+impl<T> Default for CoordMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CoordMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T> CoordMap<T> {
+    pub const fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            keys: Vec::with_capacity(capacity),
+            values: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.values.clear();
+    }
+
+    fn search(&self, coord: Coord) -> Result<usize, usize> {
+        self.keys.binary_search(&encode(coord))
+    }
+
+    /// Inserts a value, returning the value previously at `coord` if there was one.
+    pub fn insert(&mut self, coord: Coord, value: T) -> Option<T> {
+        match self.search(coord) {
+            Ok(index) => Some(core::mem::replace(&mut self.values[index], value)),
+            Err(index) => {
+                self.keys.insert(index, encode(coord));
+                self.values.insert(index, value);
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, coord: Coord) -> Option<T> {
+        let index = self.search(coord).ok()?;
+        self.keys.remove(index);
+        Some(self.values.remove(index))
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.search(coord).ok().map(|index| &self.values[index])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.search(coord).ok().map(|index| &mut self.values[index])
+    }
+
+    pub fn contains_key(&self, coord: Coord) -> bool {
+        self.search(coord).is_ok()
+    }
+
+    /// Keeps only the entries for which `f` returns true.
+    pub fn retain<F: FnMut(Coord, &mut T) -> bool>(&mut self, mut f: F) {
+        let mut keep = Vec::with_capacity(self.len());
+        for (key, value) in self.keys.iter().zip(self.values.iter_mut()) {
+            keep.push(f(decode(*key), value));
+        }
+        let mut flags = keep.iter();
+        self.keys.retain(|_| *flags.next().unwrap());
+        let mut flags = keep.iter();
+        self.values.retain(|_| *flags.next().unwrap());
+    }
+
+    /// Iterates over the entries in Morton order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            keys: self.keys.iter(),
+            values: self.values.iter(),
+        }
+    }
+
+    /// Iterates over the entries in Morton order, with mutable access to the values.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coord, &mut T)> {
+        self.keys
+            .iter()
+            .map(|&key| decode(key))
+            .zip(self.values.iter_mut())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.keys.iter().map(|&key| decode(key))
+    }
+
+    pub fn values(&self) -> slice::Iter<'_, T> {
+        self.values.iter()
+    }
+
+    /// Iterates over the entries within the rectangle with top-left corner `top_left` and
+    /// dimensions `size`, in Morton order. Cells beyond `i32::MAX` are ignored.
+    pub fn region(&self, top_left: Coord, size: Size) -> Region<'_, T> {
+        if size.is_empty() {
+            return Region {
+                keys: &[],
+                values: &[],
+                index: 0,
+                min: Coord::new(0, 0),
+                max: Coord::new(0, 0),
+            };
+        }
+        let max = Coord::new(
+            top_left.x.saturating_add_unsigned(size.width() - 1),
+            top_left.y.saturating_add_unsigned(size.height() - 1),
+        );
+        Region {
+            index: self.keys.partition_point(|&key| key < encode(top_left)),
+            keys: &self.keys,
+            values: &self.values,
+            min: top_left,
+            max,
+        }
+    }
+
+    /// Iterates over the entries at the neighbours of `coord` in `neighbourhood`.
+    pub fn neighbours(
+        &self,
+        coord: Coord,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        coord
+            .neighbours(neighbourhood)
+            .filter_map(|neighbour| self.get(neighbour).map(|value| (neighbour, value)))
+    }
+
+    /// Builds a map from entries sorted by key, keeping the last entry for each key.
+    fn from_sorted(entries: Vec<(u64, T)>) -> Self {
+        let mut map = Self::with_capacity(entries.len());
+        for (key, value) in entries {
+            if map.keys.last() == Some(&key) {
+                *map.values.last_mut().unwrap() = value;
+            } else {
+                map.keys.push(key);
+                map.values.push(value);
+            }
+        }
+        map
+    }
+
+    fn into_entries(self) -> impl Iterator<Item = (u64, T)> {
+        self.keys.into_iter().zip(self.values)
+    }
+}
+
+/// Later entries replace earlier entries with the same key, as if they were inserted in
+/// order.
+impl<T> FromIterator<(Coord, T)> for CoordMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Coord, T)>>(iter: I) -> Self {
+        let mut entries = iter
+            .into_iter()
+            .map(|(coord, value)| (encode(coord), value))
+            .collect::<Vec<_>>();
+        // The sort is stable so the last entry for each key remains last.
+        entries.sort_by_key(|&(key, _)| key);
+        Self::from_sorted(entries)
+    }
+}
+
+impl<T> Extend<(Coord, T)> for CoordMap<T> {
+    fn extend<I: IntoIterator<Item = (Coord, T)>>(&mut self, iter: I) {
+        let existing = core::mem::take(self).into_entries();
+        let mut entries = existing
+            .chain(
+                iter.into_iter()
+                    .map(|(coord, value)| (encode(coord), value)),
+            )
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|&(key, _)| key);
+        *self = Self::from_sorted(entries);
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CoordMap<T> {
+    type Item = (Coord, &'a T);
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a `CoordMap`, returned by `CoordMap::iter`.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, T> {
+    keys: slice::Iter<'a, u64>,
+    values: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Coord, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((decode(*self.keys.next()?), self.values.next()?))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// Iterator over the entries of a `CoordMap` within a rectangle, returned by
+/// `CoordMap::region`.
+#[derive(Debug, Clone)]
+pub struct Region<'a, T> {
+    keys: &'a [u64],
+    values: &'a [T],
+    index: usize,
+    min: Coord,
+    max: Coord,
+}
+
+impl<'a, T> Iterator for Region<'a, T> {
+    type Item = (Coord, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, max) = (encode(self.min), encode(self.max));
+        loop {
+            let key = *self.keys.get(self.index)?;
+            if key > max {
+                self.index = self.keys.len();
+                return None;
+            }
+            let coord = decode(key);
+            if coord.x >= self.min.x
+                && coord.x <= self.max.x
+                && coord.y >= self.min.y
+                && coord.y <= self.max.y
+            {
+                self.index += 1;
+                return Some((coord, &self.values[self.index - 1]));
+            }
+            // Skip the run of entries outside the rectangle.
+            match next_in_box(key, min, max) {
+                Some(next) => {
+                    self.index += self.keys[self.index..].partition_point(|&key| key < next)
+                }
+                None => self.index = self.keys.len(),
+            }
+        }
+    }
+}
+
+/// A set of `Coord`s supporting efficient rectangular region queries and set operations.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct CoordSet {
+    map: CoordMap<()>,
+}
+
+impl fmt::Debug for CoordSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl CoordSet {
+    pub const fn new() -> Self {
+        Self {
+            map: CoordMap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            map: CoordMap::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Returns true if `coord` was not already in the set.
+    pub fn insert(&mut self, coord: Coord) -> bool {
+        self.map.insert(coord, ()).is_none()
+    }
+
+    /// Returns true if `coord` was in the set.
+    pub fn remove(&mut self, coord: Coord) -> bool {
+        self.map.remove(coord).is_some()
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.map.contains_key(coord)
+    }
+
+    pub fn retain<F: FnMut(Coord) -> bool>(&mut self, mut f: F) {
+        self.map.retain(|coord, ()| f(coord))
+    }
+
+    /// Iterates over the coordinates in Morton order.
+    pub fn iter(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.map.keys()
+    }
+
+    /// Iterates over the coordinates within the rectangle with top-left corner `top_left` and
+    /// dimensions `size`, in Morton order.
+    pub fn region(&self, top_left: Coord, size: Size) -> impl Iterator<Item = Coord> + '_ {
+        self.map.region(top_left, size).map(|(coord, ())| coord)
+    }
+
+    /// Iterates over the neighbours of `coord` in `neighbourhood` which are in the set.
+    pub fn neighbours(
+        &self,
+        coord: Coord,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = Coord> + '_ {
+        self.map
+            .neighbours(coord, neighbourhood)
+            .map(|(coord, ())| coord)
+    }
+
+    /// Merges the sorted keys of two sets, keeping the keys for which `keep` returns true
+    /// given whether the key is in `self` and whether it's in `other`.
+    fn merge(&self, other: &Self, keep: impl Fn(bool, bool) -> bool) -> Self {
+        let (mut a, mut b) = (
+            self.map.keys.iter().peekable(),
+            other.map.keys.iter().peekable(),
+        );
+        let mut keys = Vec::new();
+        loop {
+            let (key, in_a, in_b) = match (a.peek(), b.peek()) {
+                (None, None) => break,
+                (Some(&&key), None) => (key, true, false),
+                (None, Some(&&key)) => (key, false, true),
+                (Some(&&key_a), Some(&&key_b)) => match key_a.cmp(&key_b) {
+                    Ordering::Less => (key_a, true, false),
+                    Ordering::Greater => (key_b, false, true),
+                    Ordering::Equal => (key_a, true, true),
+                },
+            };
+            if in_a {
+                a.next();
+            }
+            if in_b {
+                b.next();
+            }
+            if keep(in_a, in_b) {
+                keys.push(key);
+            }
+        }
+        let values = iter::repeat_n((), keys.len()).collect();
+        Self {
+            map: CoordMap { keys, values },
+        }
+    }
+
+    /// The coordinates in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        self.merge(other, |a, b| a || b)
+    }
+
+    /// The coordinates in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.merge(other, |a, b| a && b)
+    }
+
+    /// The coordinates in `self` but not `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.merge(other, |a, b| a && !b)
+    }
+
+    /// The coordinates in exactly one of the sets.
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.merge(other, |a, b| a != b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.iter().all(|coord| other.contains(coord))
+    }
+}
+
+impl FromIterator<Coord> for CoordSet {
+    fn from_iter<I: IntoIterator<Item = Coord>>(iter: I) -> Self {
+        Self {
+            map: iter.into_iter().map(|coord| (coord, ())).collect(),
+        }
+    }
+}
+
+impl Extend<Coord> for CoordSet {
+    fn extend<I: IntoIterator<Item = Coord>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|coord| (coord, ())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, encode, CoordMap, CoordSet};
+    use crate::coord::{Coord, Size};
+    use crate::neighbourhood::Neighbourhood;
+    use crate::test_strategies::coord_in;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn map() {
+        let mut map = CoordMap::new();
+        assert_eq!(map.insert(Coord::new(-3, 4), 'a'), None);
+        assert_eq!(map.insert(Coord::new(5, 5), 'b'), None);
+        assert_eq!(map.insert(Coord::new(-3, 4), 'c'), Some('a'));
+        assert_eq!(map.get(Coord::new(-3, 4)), Some(&'c'));
+        assert_eq!(map.len(), 2);
+        *map.get_mut(Coord::new(5, 5)).unwrap() = 'd';
+        assert_eq!(map.remove(Coord::new(5, 5)), Some('d'));
+        assert!(!map.contains_key(Coord::new(5, 5)));
+        let map = [
+            (Coord::new(0, 0), 1),
+            (Coord::new(1, 0), 2),
+            (Coord::new(0, 0), 3),
+        ]
+        .into_iter()
+        .collect::<CoordMap<_>>();
+        assert_eq!(map.get(Coord::new(0, 0)), Some(&3));
+        assert_eq!(
+            map.neighbours(Coord::new(0, 1), Neighbourhood::Eight)
+                .collect::<Vec<_>>(),
+            [(Coord::new(0, 0), &3), (Coord::new(1, 0), &2)]
+        );
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = (0..5).map(|x| Coord::new(x, 0)).collect::<CoordSet>();
+        let b = (3..8).map(|x| Coord::new(x, 0)).collect::<CoordSet>();
+        assert_eq!(a.union(&b).len(), 8);
+        assert_eq!(
+            a.intersection(&b).iter().collect::<Vec<_>>(),
+            [Coord::new(3, 0), Coord::new(4, 0)]
+        );
+        assert_eq!(a.difference(&b).len(), 3);
+        assert_eq!(a.symmetric_difference(&b).len(), 6);
+        assert!(a.intersection(&b).is_subset(&a));
+        let mut c = a.clone();
+        c.extend(b.iter());
+        assert_eq!(c, a.union(&b));
+        c.retain(|coord| coord.x % 2 == 0);
+        assert_eq!(c.len(), 4);
+    }
+
+    proptest! {
+        #[test]
+        fn morton_round_trip(x in any::<i32>(), y in any::<i32>()) {
+            prop_assert_eq!(decode(encode(Coord::new(x, y))), Coord::new(x, y));
+        }
+
+        #[test]
+        fn region_matches_brute_force(
+            coords in prop::collection::vec(coord_in(-20..20), 0..200),
+            top_left in coord_in(-20..20),
+            w in 0..20u32,
+            h in 0..20u32,
+        ) {
+            let set = coords.iter().cloned().collect::<CoordSet>();
+            let size = Size::new(w, h);
+            let expected = coords
+                .iter()
+                .cloned()
+                .filter(|&c| (c - top_left).is_valid(size))
+                .collect::<BTreeSet<_>>();
+            let actual = set.region(top_left, size).collect::<Vec<_>>();
+            prop_assert_eq!(actual.len(), expected.len());
+            prop_assert_eq!(actual.into_iter().collect::<BTreeSet<_>>(), expected);
+        }
+    }
+}
@@ -0,0 +1,521 @@
+use crate::coord::{Axis, MAX_SIZE_FIELD};
+use core::{fmt, num::IntErrorKind};
+
+const fn size_field_name(axis: Axis) -> &'static str {
+    match axis {
+        Axis::X => "width",
+        Axis::Y => "height",
+    }
+}
+
+const fn coord_field_name(axis: Axis) -> &'static str {
+    match axis {
+        Axis::X => "x",
+        Axis::Y => "y",
+    }
+}
+
+/// A panic message assembled in a `const fn`, where `format!` isn't available.
+pub(crate) struct ConstMessage {
+    buf: [u8; Self::CAPACITY],
+    len: usize,
+}
+
+impl ConstMessage {
+    const CAPACITY: usize = 128;
+
+    pub(crate) const fn new() -> Self {
+        Self {
+            buf: [0; Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    pub(crate) const fn str(mut self, s: &str) -> Self {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            self.buf[self.len] = bytes[i];
+            self.len += 1;
+            i += 1;
+        }
+        self
+    }
+
+    pub(crate) const fn int(mut self, value: i64) -> Self {
+        if value < 0 {
+            self.buf[self.len] = b'-';
+            self.len += 1;
+        }
+        let mut magnitude = value.unsigned_abs();
+        let mut digits = [0; 20];
+        let mut num_digits = 0;
+        loop {
+            digits[num_digits] = b'0' + (magnitude % 10) as u8;
+            num_digits += 1;
+            magnitude /= 10;
+            if magnitude == 0 {
+                break;
+            }
+        }
+        while num_digits > 0 {
+            num_digits -= 1;
+            self.buf[self.len] = digits[num_digits];
+            self.len += 1;
+        }
+        self
+    }
+
+    pub(crate) const fn panic(&self) -> ! {
+        let (message, _) = self.buf.split_at(self.len);
+        match core::str::from_utf8(message) {
+            Ok(message) => panic!("{}", message),
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+/// A `Coord` with a negative component was converted into a `Size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NegativeDimension {
+    pub axis: Axis,
+    pub value: i32,
+}
+
+impl fmt::Display for NegativeDimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} component {} is negative so can't be used as a size",
+            coord_field_name(self.axis),
+            self.value
+        )
+    }
+}
+
+impl core::error::Error for NegativeDimension {}
+
+/// A dimension was greater than `MAX_SIZE_FIELD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionTooLargeForSize {
+    pub axis: Axis,
+    pub value: u32,
+}
+
+impl DimensionTooLargeForSize {
+    pub const LIMIT: u32 = MAX_SIZE_FIELD;
+
+    /// Panics with the same message as `Display`. This can be called from a `const fn`.
+    pub(crate) const fn panic(self) -> ! {
+        ConstMessage::new()
+            .str(size_field_name(self.axis))
+            .str(" ")
+            .int(self.value as i64)
+            .str(" is too large for a size (max ")
+            .int(Self::LIMIT as i64)
+            .str(")")
+            .panic()
+    }
+}
+
+impl fmt::Display for DimensionTooLargeForSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} is too large for a size (max {})",
+            size_field_name(self.axis),
+            self.value,
+            Self::LIMIT
+        )
+    }
+}
+
+impl core::error::Error for DimensionTooLargeForSize {}
+
+/// A dimension of a `Size` was greater than `i32::MAX` so can't be used as a `Coord`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionTooLargeForCoord {
+    pub axis: Axis,
+    pub value: u32,
+}
+
+impl DimensionTooLargeForCoord {
+    pub const LIMIT: u32 = i32::MAX as u32;
+}
+
+impl fmt::Display for DimensionTooLargeForCoord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} is too large for a coord (max {})",
+            size_field_name(self.axis),
+            self.value,
+            Self::LIMIT
+        )
+    }
+}
+
+impl core::error::Error for DimensionTooLargeForCoord {}
+
+/// The result of a fallible arithmetic operation between `Coord`s and `Size`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// A component of the resulting `Coord` would not fit in an `i32`.
+    CoordOutOfRange {
+        axis: Axis,
+        value: i64,
+    },
+    /// A component of the resulting `Size` would be negative or greater than
+    /// `MAX_SIZE_FIELD`.
+    SizeOutOfRange {
+        axis: Axis,
+        value: i64,
+    },
+    DivisionByZero,
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::CoordOutOfRange { axis, value } => write!(
+                f,
+                "resulting {} component {} is out of range for a coord",
+                coord_field_name(axis),
+                value
+            ),
+            Self::SizeOutOfRange { axis, value } => write!(
+                f,
+                "resulting {} {} is out of range for a size (min 0, max {})",
+                size_field_name(axis),
+                value,
+                MAX_SIZE_FIELD
+            ),
+            Self::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl core::error::Error for ArithmeticError {}
+
+/// The type whose text representation contained an invalid integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseTarget {
+    Coord,
+    Size,
+}
+
+/// The reason a string could not be parsed as a `Coord`, `Size`, `Axis` or `Rule`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input did not match the grammar. `position` is the byte offset into the input at
+    /// which `expected` was expected.
+    Syntax {
+        expected: &'static str,
+        position: usize,
+    },
+    /// The component of a `Coord`, or dimension of a `Size`, along `axis` was not a valid
+    /// integer of the required type.
+    InvalidInteger {
+        target: ParseTarget,
+        axis: Axis,
+        kind: IntErrorKind,
+    },
+    /// The dimensions were valid integers but too large for a `Size`.
+    DimensionTooLargeForSize(DimensionTooLargeForSize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Syntax { expected, position } => {
+                write!(f, "expected {} at position {}", expected, position)
+            }
+            Self::InvalidInteger { target, axis, kind } => {
+                let reason = match kind {
+                    IntErrorKind::Empty => "is missing",
+                    IntErrorKind::InvalidDigit => "contains an invalid digit",
+                    IntErrorKind::PosOverflow => "is too large",
+                    IntErrorKind::NegOverflow => "is too small",
+                    _ => "is not a valid integer",
+                };
+                match target {
+                    ParseTarget::Coord => {
+                        write!(f, "{} component {}", coord_field_name(*axis), reason)
+                    }
+                    ParseTarget::Size => write!(f, "{} {}", size_field_name(*axis), reason),
+                }
+            }
+            Self::DimensionTooLargeForSize(e) => e.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::DimensionTooLargeForSize(e) => e.source(),
+            _ => None,
+        }
+    }
+}
+
+/// A `Coord` or `Size` could not be written in a binary encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The output buffer ended before the encoding was complete.
+    BufferTooSmall,
+    /// A delta sequence contained more than `u32::MAX` coordinates.
+    TooManyCoords,
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall => write!(f, "output buffer is too small"),
+            Self::TooManyCoords => write!(f, "too many coords in sequence"),
+        }
+    }
+}
+
+impl core::error::Error for EncodeError {}
+
+/// A `Coord` or `Size` could not be read from a binary encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input ended before the encoding was complete.
+    UnexpectedEnd,
+    /// A varint encoded a value which doesn't fit in 32 bits.
+    VarintOverflow,
+    /// A delta sequence was written with a format version this crate doesn't understand.
+    UnsupportedVersion(u8),
+    /// A decoded dimension was too large for a `Size`.
+    DimensionTooLargeForSize(DimensionTooLargeForSize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Self::VarintOverflow => write!(f, "varint does not fit in 32 bits"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version {}", version)
+            }
+            Self::DimensionTooLargeForSize(e) => e.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::DimensionTooLargeForSize(e) => e.source(),
+            _ => None,
+        }
+    }
+}
+
+/// Any of the errors produced by this crate.
+///
+/// Each variant wraps another error transparently: it's displayed as the inner error, and its
+/// source is the inner error's source rather than the inner error itself, so reports built by
+/// following `source` don't repeat the message. The variants of `ParseError` and
+/// `DecodeError` which wrap another error behave the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    NegativeDimension(NegativeDimension),
+    DimensionTooLargeForSize(DimensionTooLargeForSize),
+    DimensionTooLargeForCoord(DimensionTooLargeForCoord),
+    Arithmetic(ArithmeticError),
+    Parse(ParseError),
+    Encode(EncodeError),
+    Decode(DecodeError),
+}
+
+impl Error {
+    /// The axis along which the offending value was found, if there is one.
+    pub const fn axis(&self) -> Option<Axis> {
+        match *self {
+            Self::NegativeDimension(NegativeDimension { axis, .. })
+            | Self::DimensionTooLargeForSize(DimensionTooLargeForSize { axis, .. })
+            | Self::DimensionTooLargeForCoord(DimensionTooLargeForCoord { axis, .. })
+            | Self::Arithmetic(ArithmeticError::CoordOutOfRange { axis, .. })
+            | Self::Arithmetic(ArithmeticError::SizeOutOfRange { axis, .. }) => Some(axis),
+            Self::Parse(ParseError::InvalidInteger { axis, .. })
+            | Self::Parse(ParseError::DimensionTooLargeForSize(DimensionTooLargeForSize {
+                axis,
+                ..
+            })) => Some(axis),
+            Self::Decode(DecodeError::DimensionTooLargeForSize(DimensionTooLargeForSize {
+                axis,
+                ..
+            })) => Some(axis),
+            Self::Arithmetic(ArithmeticError::DivisionByZero)
+            | Self::Parse(ParseError::Syntax { .. })
+            | Self::Encode(_)
+            | Self::Decode(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NegativeDimension(e) => e.fmt(f),
+            Self::DimensionTooLargeForSize(e) => e.fmt(f),
+            Self::DimensionTooLargeForCoord(e) => e.fmt(f),
+            Self::Arithmetic(e) => e.fmt(f),
+            Self::Parse(e) => e.fmt(f),
+            Self::Encode(e) => e.fmt(f),
+            Self::Decode(e) => e.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::NegativeDimension(e) => e.source(),
+            Self::DimensionTooLargeForSize(e) => e.source(),
+            Self::DimensionTooLargeForCoord(e) => e.source(),
+            Self::Arithmetic(e) => e.source(),
+            Self::Parse(e) => e.source(),
+            Self::Encode(e) => e.source(),
+            Self::Decode(e) => e.source(),
+        }
+    }
+}
+
+impl From<NegativeDimension> for Error {
+    fn from(e: NegativeDimension) -> Self {
+        Self::NegativeDimension(e)
+    }
+}
+
+impl From<DimensionTooLargeForSize> for Error {
+    fn from(e: DimensionTooLargeForSize) -> Self {
+        Self::DimensionTooLargeForSize(e)
+    }
+}
+
+impl From<DimensionTooLargeForCoord> for Error {
+    fn from(e: DimensionTooLargeForCoord) -> Self {
+        Self::DimensionTooLargeForCoord(e)
+    }
+}
+
+impl From<ArithmeticError> for Error {
+    fn from(e: ArithmeticError) -> Self {
+        Self::Arithmetic(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<EncodeError> for Error {
+    fn from(e: EncodeError) -> Self {
+        Self::Encode(e)
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(e: DecodeError) -> Self {
+        Self::Decode(e)
+    }
+}
+
+impl From<DimensionTooLargeForSize> for DecodeError {
+    fn from(e: DimensionTooLargeForSize) -> Self {
+        Self::DimensionTooLargeForSize(e)
+    }
+}
+
+impl From<DimensionTooLargeForSize> for ParseError {
+    fn from(e: DimensionTooLargeForSize) -> Self {
+        Self::DimensionTooLargeForSize(e)
+    }
+}
+
+impl From<DimensionTooLargeForSize> for ArithmeticError {
+    fn from(DimensionTooLargeForSize { axis, value }: DimensionTooLargeForSize) -> Self {
+        Self::SizeOutOfRange {
+            axis,
+            value: value as i64,
+        }
+    }
+}
+
+impl From<DimensionTooLargeForCoord> for ArithmeticError {
+    fn from(DimensionTooLargeForCoord { axis, value }: DimensionTooLargeForCoord) -> Self {
+        Self::CoordOutOfRange {
+            axis,
+            value: value as i64,
+        }
+    }
+}
+
+impl From<NegativeDimension> for ArithmeticError {
+    fn from(NegativeDimension { axis, value }: NegativeDimension) -> Self {
+        Self::SizeOutOfRange {
+            axis,
+            value: value as i64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coord::{Coord, Size};
+
+    #[test]
+    fn payloads() {
+        assert_eq!(
+            Coord::new(3, -4).to_size(),
+            Err(NegativeDimension {
+                axis: Axis::Y,
+                value: -4
+            })
+        );
+        assert_eq!(
+            Size::try_new(1, u32::MAX),
+            Err(DimensionTooLargeForSize {
+                axis: Axis::Y,
+                value: u32::MAX
+            })
+        );
+        assert_eq!(
+            Size::new(MAX_SIZE_FIELD, 0).to_coord(),
+            Err(DimensionTooLargeForCoord {
+                axis: Axis::X,
+                value: MAX_SIZE_FIELD
+            })
+        );
+        let e: Error = Size::new(1, 1).try_div(0).unwrap_err().into();
+        assert_eq!(e.axis(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display() {
+        let e = Size::try_new(u32::MAX, 0).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "width 4294967295 is too large for a size (max 2147483648)"
+        );
+        let e: Box<dyn std::error::Error> = Box::new(Error::from(e));
+        assert_eq!(
+            e.to_string(),
+            "width 4294967295 is too large for a size (max 2147483648)"
+        );
+        assert!(e.source().is_none());
+        let e = Error::Parse(ParseError::DimensionTooLargeForSize(
+            Size::try_new(0, u32::MAX).unwrap_err(),
+        ));
+        assert!(core::error::Error::source(&e).is_none());
+    }
+}
@@ -0,0 +1,245 @@
+//! Scaling a `Size`, fitting one size inside another while keeping its aspect ratio, and
+//! aligning a size within another.
+
+use crate::coord::{Axis, Coord, Size, MAX_SIZE_FIELD};
+
+/// Where to position a child within its parent along one axis.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum Align {
+    /// At the lowest position, i.e. left or top.
+    #[default]
+    Start,
+    /// In the middle. If the space around the child can't be split evenly, the extra cell is
+    /// after the child.
+    Centre,
+    /// At the highest position, i.e. right or bottom.
+    End,
+}
+
+impl Align {
+    /// The offset of a child of length `child` from the start of a parent of length `parent`.
+    /// This is negative if the child is longer than the parent and isn't aligned to the start.
+    /// Offsets which can't be represented by an `i32` saturate.
+    pub const fn offset(self, child: u32, parent: u32) -> i32 {
+        let space = parent as i64 - child as i64;
+        let offset = match self {
+            Self::Start => 0,
+            Self::Centre => space.div_euclid(2),
+            Self::End => space,
+        };
+        if offset > i32::MAX as i64 {
+            i32::MAX
+        } else {
+            offset as i32
+        }
+    }
+}
+
+impl Size {
+    /// Multiplies each dimension by `numerator / denominator`, rounding down.
+    /// Panics if `denominator` is 0 or the result is too large for a `Size`.
+    pub const fn scale(self, numerator: u32, denominator: u32) -> Self {
+        match self.checked_scale(numerator, denominator) {
+            Some(size) => size,
+            None => panic!("scaled size is too large or denominator is 0"),
+        }
+    }
+
+    /// Multiplies each dimension by `numerator / denominator`, rounding down. Returns `None` if
+    /// `denominator` is 0 or the result is too large for a `Size`.
+    pub const fn checked_scale(self, numerator: u32, denominator: u32) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let x = self.width() as u64 * numerator as u64 / denominator as u64;
+        let y = self.height() as u64 * numerator as u64 / denominator as u64;
+        if x > MAX_SIZE_FIELD as u64 || y > MAX_SIZE_FIELD as u64 {
+            None
+        } else {
+            Some(Size::new(x as u32, y as u32))
+        }
+    }
+
+    /// The largest `n` such that `self * n` fits within `container`, which is 0 if `self` is
+    /// larger than `container` along either axis. A dimension of 0 fits at any scale, so this
+    /// returns `None` if `self` is zero.
+    pub const fn integer_scale_to_fit(self, container: Self) -> Option<u32> {
+        match (
+            container.width().checked_div(self.width()),
+            container.height().checked_div(self.height()),
+        ) {
+            (Some(x), Some(y)) => Some(if x < y { x } else { y }),
+            (Some(scale), None) | (None, Some(scale)) => Some(scale),
+            (None, None) => None,
+        }
+    }
+
+    /// The largest size with the same aspect ratio as `self` which fits within `container`,
+    /// scaling up or down as needed and rounding down. A zero size stays zero, and a size with
+    /// one zero dimension is stretched along the other.
+    pub const fn scale_to_fit(self, container: Self) -> Self {
+        let (w, h) = (self.width() as u64, self.height() as u64);
+        let (cw, ch) = (container.width() as u64, container.height() as u64);
+        if w == 0 && h == 0 {
+            return self;
+        }
+        if h == 0 {
+            return Size::new(cw as u32, 0);
+        }
+        if w == 0 {
+            return Size::new(0, ch as u32);
+        }
+        // Compare the aspect ratios w/h and cw/ch without dividing.
+        if w * ch <= cw * h {
+            // Limited by the container's height.
+            Size::new((w * ch / h) as u32, ch as u32)
+        } else {
+            // Limited by the container's width.
+            Size::new(cw as u32, (h * cw / w) as u32)
+        }
+    }
+
+    /// Scales `self` to fit within `container` keeping its aspect ratio, then centres it,
+    /// returning the offset from the top-left of `container` and the scaled size. Any space
+    /// not covered is the letterbox (or pillarbox) border.
+    pub const fn letterbox(self, container: Self) -> (Coord, Self) {
+        let size = self.scale_to_fit(container);
+        (
+            size.align_within(container, Align::Centre, Align::Centre),
+            size,
+        )
+    }
+
+    /// Like `letterbox` but only scales by a whole number, so each cell of `self` covers the
+    /// same number of cells of `container`. Returns the offset, the scale and the scaled size.
+    /// If `self` doesn't fit at a scale of 1 it isn't scaled.
+    pub const fn integer_letterbox(self, container: Self) -> (Coord, u32, Self) {
+        let scale = match self.integer_scale_to_fit(container) {
+            Some(0) | None => 1,
+            Some(scale) => scale,
+        };
+        let size = self.const_mul(scale);
+        (
+            size.align_within(container, Align::Centre, Align::Centre),
+            scale,
+            size,
+        )
+    }
+
+    /// The offset from the top-left corner of `parent` at which to place `self` so it's
+    /// aligned by `x` horizontally and `y` vertically.
+    pub const fn align_within(self, parent: Self, x: Align, y: Align) -> Coord {
+        Coord::new(
+            x.offset(self.width(), parent.width()),
+            y.offset(self.height(), parent.height()),
+        )
+    }
+
+    /// The offset along `axis` at which to place `self` within `parent`, aligned by `align`.
+    pub const fn align_within_axis(self, parent: Self, axis: Axis, align: Align) -> i32 {
+        align.offset(self.get(axis), parent.get(axis))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Align;
+    use crate::coord::{Axis, Coord, Size};
+
+    #[test]
+    fn scale() {
+        assert_eq!(Size::new(10, 7).scale(3, 2), Size::new(15, 10));
+        assert_eq!(Size::new(10, 7).checked_scale(1, 0), None);
+        assert_eq!(Size::max().checked_scale(2, 1), None);
+        assert_eq!(
+            Size::new(320, 180).integer_scale_to_fit(Size::new(1366, 768)),
+            Some(4)
+        );
+        assert_eq!(
+            Size::new(320, 180).integer_scale_to_fit(Size::new(300, 768)),
+            Some(0)
+        );
+        assert_eq!(
+            Size::new(0, 10).integer_scale_to_fit(Size::new(5, 35)),
+            Some(3)
+        );
+        assert_eq!(Size::new(0, 0).integer_scale_to_fit(Size::new(5, 35)), None);
+        assert_eq!(
+            Size::new(4, 3).scale_to_fit(Size::new(100, 60)),
+            Size::new(80, 60)
+        );
+        assert_eq!(
+            Size::new(4, 3).scale_to_fit(Size::new(40, 60)),
+            Size::new(40, 30)
+        );
+        assert_eq!(
+            Size::new(0, 3).scale_to_fit(Size::new(40, 60)),
+            Size::new(0, 60)
+        );
+        assert_eq!(
+            Size::new(3, 0).scale_to_fit(Size::new(40, 60)),
+            Size::new(40, 0)
+        );
+        assert_eq!(
+            Size::new(0, 0).scale_to_fit(Size::new(40, 60)),
+            Size::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn scale_to_fit_zero_height() {
+        assert_eq!(
+            Size::new(3, 0).scale_to_fit(Size::new(40, 0)),
+            Size::new(40, 0)
+        );
+        assert_eq!(
+            Size::new(3, 0).scale_to_fit(Size::new(0, 0)),
+            Size::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn scale_to_fit_zero_width() {
+        assert_eq!(
+            Size::new(0, 3).scale_to_fit(Size::new(0, 40)),
+            Size::new(0, 40)
+        );
+        assert_eq!(
+            Size::new(0, 3).scale_to_fit(Size::new(0, 0)),
+            Size::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn letterbox() {
+        assert_eq!(
+            Size::new(16, 9).letterbox(Size::new(100, 100)),
+            (Coord::new(0, 22), Size::new(100, 56))
+        );
+        assert_eq!(
+            Size::new(320, 180).integer_letterbox(Size::new(1366, 768)),
+            (Coord::new(43, 24), 4, Size::new(1280, 720))
+        );
+        assert_eq!(
+            Size::new(320, 180).integer_letterbox(Size::new(300, 100)),
+            (Coord::new(-10, -40), 1, Size::new(320, 180))
+        );
+    }
+
+    #[test]
+    fn align() {
+        let parent = Size::new(10, 5);
+        let child = Size::new(3, 2);
+        assert_eq!(
+            child.align_within(parent, Align::Start, Align::End),
+            Coord::new(0, 3)
+        );
+        assert_eq!(
+            child.align_within(parent, Align::Centre, Align::Centre),
+            Coord::new(3, 1)
+        );
+        assert_eq!(child.align_within_axis(parent, Axis::X, Align::End), 7);
+        assert_eq!(Align::Centre.offset(5, 2), -2);
+        assert_eq!(Align::End.offset(0, crate::MAX_SIZE_FIELD), i32::MAX);
+    }
+}
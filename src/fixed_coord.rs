@@ -0,0 +1,263 @@
+use crate::coord::{Coord, Size};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// A coordinate with `FRAC_BITS` bits of precision below the level of a cell.
+/// Internally this is a `Coord` scaled up by `2^FRAC_BITS`, so all arithmetic is integer
+/// arithmetic and gives the same result on every platform.
+/// `FRAC_BITS` must be less than 31. Using a `FixedCoord` with more is a compile error:
+///
+/// ```compile_fail
+/// let _ = coord_2d::FixedCoord::<40>::new_raw(1, 2).to_coord();
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct FixedCoord<const FRAC_BITS: u32> {
+    raw: Coord,
+}
+
+impl<const FRAC_BITS: u32> FixedCoord<FRAC_BITS> {
+    /// Evaluated by every constructor and every method which shifts by `FRAC_BITS`, so an
+    /// invalid `FRAC_BITS` fails to compile rather than producing wrong results.
+    const VALID: () = assert!(FRAC_BITS < 31, "FRAC_BITS must be less than 31");
+
+    /// The raw value corresponding to a distance of one cell.
+    pub const ONE: i32 = {
+        let () = Self::VALID;
+        1 << FRAC_BITS
+    };
+
+    const MASK: i32 = Self::ONE - 1;
+
+    pub const fn from_raw(raw: Coord) -> Self {
+        let () = Self::VALID;
+        Self { raw }
+    }
+
+    pub const fn new_raw(x: i32, y: i32) -> Self {
+        Self::from_raw(Coord::new(x, y))
+    }
+
+    pub const fn raw(self) -> Coord {
+        self.raw
+    }
+
+    /// Returns the position of the top-left corner of the cell at `coord`.
+    pub fn from_coord(coord: Coord) -> Self {
+        Self::from_raw(coord * Self::ONE)
+    }
+
+    /// Returns the position of the centre of the cell at `coord`.
+    pub fn from_coord_centre(coord: Coord) -> Self {
+        Self::from_cell_and_offset(coord, Coord::new(Self::ONE / 2, Self::ONE / 2))
+    }
+
+    /// Inverse of `split`. Offset components outside `0..ONE` carry into the cell.
+    pub fn from_cell_and_offset(cell: Coord, offset: Coord) -> Self {
+        Self::from_raw(cell * Self::ONE + offset)
+    }
+
+    /// Returns the coordinate of the cell containing this position.
+    pub const fn to_coord(self) -> Coord {
+        let () = Self::VALID;
+        Coord::new(self.raw.x >> FRAC_BITS, self.raw.y >> FRAC_BITS)
+    }
+
+    /// Returns the coordinate of the cell whose top-left corner is nearest to this position.
+    pub const fn round_to_coord(self) -> Coord {
+        // Widened so that adding a half doesn't overflow for positions in the last cell
+        // before `i32::MAX`. Shifting back down always brings the result into range.
+        const fn round_part(raw: i32, frac_bits: u32) -> i32 {
+            ((raw as i64 + (1 << frac_bits) / 2) >> frac_bits) as i32
+        }
+        let () = Self::VALID;
+        Coord::new(
+            round_part(self.raw.x, FRAC_BITS),
+            round_part(self.raw.y, FRAC_BITS),
+        )
+    }
+
+    /// Returns the offset of this position within its cell. Each component is in the range
+    /// `0..ONE`, including for positions with negative components.
+    pub const fn cell_offset(self) -> Coord {
+        Coord::new(self.raw.x & Self::MASK, self.raw.y & Self::MASK)
+    }
+
+    /// Returns the cell containing this position and the offset within that cell.
+    pub const fn split(self) -> (Coord, Coord) {
+        (self.to_coord(), self.cell_offset())
+    }
+
+    pub const fn is_valid(self, size: Size) -> bool {
+        self.to_coord().is_valid(size)
+    }
+
+    /// Linearly interpolates between `self` and `other`. `t` is a fixed-point fraction with
+    /// the same number of fractional bits, so `0` returns `self` and `ONE` returns `other`.
+    pub const fn lerp(self, other: Self, t: i32) -> Self {
+        const fn lerp_part(from: i32, to: i32, t: i32, frac_bits: u32) -> i32 {
+            let delta = ((to as i64 - from as i64) * t as i64) >> frac_bits;
+            (from as i64 + delta) as i32
+        }
+        Self::new_raw(
+            lerp_part(self.raw.x, other.raw.x, t, FRAC_BITS),
+            lerp_part(self.raw.y, other.raw.y, t, FRAC_BITS),
+        )
+    }
+}
+
+impl<const FRAC_BITS: u32> From<Coord> for FixedCoord<FRAC_BITS> {
+    fn from(coord: Coord) -> Self {
+        Self::from_coord(coord)
+    }
+}
+
+impl<const FRAC_BITS: u32> Neg for FixedCoord<FRAC_BITS> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::from_raw(-self.raw)
+    }
+}
+
+impl<const FRAC_BITS: u32> Add for FixedCoord<FRAC_BITS> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_raw(self.raw + rhs.raw)
+    }
+}
+
+impl<const FRAC_BITS: u32> Add<Coord> for FixedCoord<FRAC_BITS> {
+    type Output = Self;
+    fn add(self, rhs: Coord) -> Self::Output {
+        Self::from_raw(self.raw + rhs * Self::ONE)
+    }
+}
+
+impl<const FRAC_BITS: u32> Add<Size> for FixedCoord<FRAC_BITS> {
+    type Output = Self;
+    fn add(self, rhs: Size) -> Self::Output {
+        Self::from_raw(self.raw + rhs * Self::ONE as u32)
+    }
+}
+
+impl<T, const FRAC_BITS: u32> AddAssign<T> for FixedCoord<FRAC_BITS>
+where
+    FixedCoord<FRAC_BITS>: Add<T, Output = FixedCoord<FRAC_BITS>>,
+{
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const FRAC_BITS: u32> Sub for FixedCoord<FRAC_BITS> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_raw(self.raw - rhs.raw)
+    }
+}
+
+impl<const FRAC_BITS: u32> Sub<Coord> for FixedCoord<FRAC_BITS> {
+    type Output = Self;
+    fn sub(self, rhs: Coord) -> Self::Output {
+        Self::from_raw(self.raw - rhs * Self::ONE)
+    }
+}
+
+impl<const FRAC_BITS: u32> Sub<Size> for FixedCoord<FRAC_BITS> {
+    type Output = Self;
+    fn sub(self, rhs: Size) -> Self::Output {
+        Self::from_raw(self.raw - rhs * Self::ONE as u32)
+    }
+}
+
+impl<T, const FRAC_BITS: u32> SubAssign<T> for FixedCoord<FRAC_BITS>
+where
+    FixedCoord<FRAC_BITS>: Sub<T, Output = FixedCoord<FRAC_BITS>>,
+{
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const FRAC_BITS: u32> Mul<i32> for FixedCoord<FRAC_BITS> {
+    type Output = Self;
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self::from_raw(self.raw * rhs)
+    }
+}
+
+impl<T, const FRAC_BITS: u32> MulAssign<T> for FixedCoord<FRAC_BITS>
+where
+    FixedCoord<FRAC_BITS>: Mul<T, Output = FixedCoord<FRAC_BITS>>,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const FRAC_BITS: u32> Div<i32> for FixedCoord<FRAC_BITS> {
+    type Output = Self;
+    fn div(self, rhs: i32) -> Self::Output {
+        Self::from_raw(self.raw / rhs)
+    }
+}
+
+impl<T, const FRAC_BITS: u32> DivAssign<T> for FixedCoord<FRAC_BITS>
+where
+    FixedCoord<FRAC_BITS>: Div<T, Output = FixedCoord<FRAC_BITS>>,
+{
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FixedCoord;
+    use crate::coord::{Coord, Size};
+
+    type F8 = FixedCoord<8>;
+
+    #[test]
+    fn split() {
+        let f = F8::from_cell_and_offset(Coord::new(-3, 2), Coord::new(17, 255));
+        assert_eq!(f.split(), (Coord::new(-3, 2), Coord::new(17, 255)));
+        let f = F8::from_coord(Coord::new(-1, 0)) + F8::new_raw(1, -1);
+        assert_eq!(f.to_coord(), Coord::new(-1, -1));
+        assert_eq!(f.cell_offset(), Coord::new(1, 255));
+        assert_eq!(
+            F8::from_coord_centre(Coord::new(4, 5)).round_to_coord(),
+            Coord::new(5, 6)
+        );
+        assert_eq!(
+            F8::new_raw(i32::MAX, i32::MIN).round_to_coord(),
+            Coord::new((i32::MAX >> 8) + 1, i32::MIN >> 8)
+        );
+        assert_eq!(
+            F8::new_raw(i32::MAX - 128, 0).round_to_coord(),
+            Coord::new(i32::MAX >> 8, 0)
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut f = F8::from_coord(Coord::new(1, 1));
+        f += Coord::new(2, -3);
+        f += Size::new(1, 1);
+        assert_eq!(f.to_coord(), Coord::new(4, -1));
+        f -= F8::new_raw(128, 0);
+        assert_eq!(f.split(), (Coord::new(3, -1), Coord::new(128, 0)));
+        assert_eq!((f * 2).to_coord(), Coord::new(7, -2));
+    }
+
+    #[test]
+    fn lerp() {
+        let a = F8::from_coord(Coord::new(0, 10));
+        let b = F8::from_coord(Coord::new(10, -10));
+        assert_eq!(a.lerp(b, 0), a);
+        assert_eq!(a.lerp(b, F8::ONE), b);
+        assert_eq!(a.lerp(b, F8::ONE / 2).to_coord(), Coord::new(5, 0));
+    }
+}
@@ -0,0 +1,250 @@
+//! Finding the connected regions of cells within a `Size` which satisfy a predicate.
+
+use crate::coord::{Coord, Size};
+use crate::coord_bit_set::CoordBitSet;
+use crate::neighbourhood::Neighbourhood;
+use alloc::{vec, vec::Vec};
+
+/// The bounding rectangle and number of cells of a connected region.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Component {
+    pub top_left: Coord,
+    pub size: Size,
+    pub count: usize,
+}
+
+/// The cells of a connected region, along with its bounding rectangle and number of cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilledRegion {
+    pub cells: CoordBitSet,
+    pub component: Component,
+}
+
+/// Visits the cells connected to `start` by `neighbourhood` which `claim` returns true for.
+/// `claim` is called for each cell which could be part of the region, and must return false
+/// for cells it has already returned true for.
+fn fill<F: FnMut(Coord) -> bool>(
+    size: Size,
+    start: Coord,
+    neighbourhood: Neighbourhood,
+    mut claim: F,
+) -> Option<Component> {
+    if !start.is_valid(size) || !claim(start) {
+        return None;
+    }
+    let mut stack = vec![start];
+    let (mut min, mut max) = (start, start);
+    let mut count = 0;
+    while let Some(coord) = stack.pop() {
+        count += 1;
+        min = min.pairwise_min(coord);
+        max = max.pairwise_max(coord);
+        for neighbour in coord.neighbours(neighbourhood) {
+            if neighbour.is_valid(size) && claim(neighbour) {
+                stack.push(neighbour);
+            }
+        }
+    }
+    Some(Component {
+        top_left: min,
+        size: Size::new(max.x.abs_diff(min.x) + 1, max.y.abs_diff(min.y) + 1),
+        count,
+    })
+}
+
+impl Size {
+    /// The region of cells connected to `start` by `neighbourhood` for which `f` returns true,
+    /// or `None` if `start` is outside this size or `f` returns false for it. `f` may be
+    /// called more than once for cells outside the region.
+    pub fn flood_fill<F: FnMut(Coord) -> bool>(
+        self,
+        start: Coord,
+        neighbourhood: Neighbourhood,
+        mut f: F,
+    ) -> Option<FilledRegion> {
+        let mut cells = CoordBitSet::new(self);
+        let component = fill(self, start, neighbourhood, |coord| {
+            !cells.contains(coord) && f(coord) && cells.insert(coord)
+        })?;
+        Some(FilledRegion { cells, component })
+    }
+
+    /// Labels every maximal region of cells connected by `neighbourhood` for which `f`
+    /// returns true. `f` is called once for each cell.
+    pub fn connected_components<F: FnMut(Coord) -> bool>(
+        self,
+        neighbourhood: Neighbourhood,
+        mut f: F,
+    ) -> Components {
+        let mut included = CoordBitSet::new(self);
+        for coord in self.coord_iter_row_major() {
+            if f(coord) {
+                included.insert(coord);
+            }
+        }
+        let mut labels = vec![None; self.count()];
+        let mut components = Vec::new();
+        for start in included.iter() {
+            if labels[index(self, start)].is_some() {
+                continue;
+            }
+            let label = u32::try_from(components.len()).expect("too many components");
+            let component = fill(self, start, neighbourhood, |coord| {
+                let slot = &mut labels[index(self, coord)];
+                if slot.is_none() && included.contains(coord) {
+                    *slot = Some(label);
+                    true
+                } else {
+                    false
+                }
+            });
+            components.extend(component);
+        }
+        Components {
+            size: self,
+            labels,
+            components,
+        }
+    }
+}
+
+/// The row-major index of a coordinate within `size`.
+fn index(size: Size, coord: Coord) -> usize {
+    coord.y as usize * size.width() as usize + coord.x as usize
+}
+
+/// The connected regions of a `Size`, returned by `Size::connected_components`. Regions are
+/// labelled from 0 in the row-major order of their first cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    size: Size,
+    labels: Vec<Option<u32>>,
+    components: Vec<Component>,
+}
+
+impl Components {
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// The number of regions.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// The label of the region containing `coord`, or `None` if it's not in a region.
+    pub fn label(&self, coord: Coord) -> Option<usize> {
+        if !coord.is_valid(self.size) {
+            return None;
+        }
+        self.labels[index(self.size, coord)].map(|label| label as usize)
+    }
+
+    /// The bounding rectangle and number of cells of the region labelled `label`.
+    pub fn get(&self, label: usize) -> Option<&Component> {
+        self.components.get(label)
+    }
+
+    /// The bounding rectangles and numbers of cells of each region, indexed by label.
+    pub fn components(&self) -> &[Component] {
+        &self.components
+    }
+
+    /// The cells of the region labelled `label`. Panics if there's no such region.
+    pub fn cells(&self, label: usize) -> CoordBitSet {
+        let component = &self.components[label];
+        let mut cells = CoordBitSet::new(self.size);
+        for offset in component.size.coord_iter_row_major() {
+            let coord = component.top_left + offset;
+            if self.labels[index(self.size, coord)] == Some(label as u32) {
+                cells.insert(coord);
+            }
+        }
+        cells
+    }
+
+    /// The region with the most cells, or `None` if there are no regions. Ties are broken by
+    /// the lowest label.
+    pub fn largest(&self) -> Option<(usize, &Component)> {
+        self.components
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, component)| component.count)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::coord::{Coord, Size};
+    use crate::neighbourhood::Neighbourhood;
+
+    const MAP: &[&str] = &[
+        "##....#", //
+        "#.#...#", //
+        ".#.....", //
+        "......#", //
+    ];
+
+    fn wall(coord: Coord) -> bool {
+        MAP[coord.y as usize].as_bytes()[coord.x as usize] == b'#'
+    }
+
+    #[test]
+    fn flood_fill() {
+        let size = Size::new(7, 4);
+        let region = size
+            .flood_fill(Coord::new(0, 0), Neighbourhood::Four, wall)
+            .unwrap();
+        assert_eq!(region.component.count, 3);
+        assert_eq!(region.component.size, Size::new(2, 2));
+        assert_eq!(
+            region.cells.iter().collect::<Vec<_>>(),
+            [Coord::new(0, 0), Coord::new(1, 0), Coord::new(0, 1)]
+        );
+        let region = size
+            .flood_fill(Coord::new(0, 0), Neighbourhood::Eight, wall)
+            .unwrap();
+        assert_eq!(region.component.count, 5);
+        assert_eq!(region.component.top_left, Coord::new(0, 0));
+        assert_eq!(region.component.size, Size::new(3, 3));
+        assert!(size
+            .flood_fill(Coord::new(2, 0), Neighbourhood::Four, wall)
+            .is_none());
+        assert!(size
+            .flood_fill(Coord::new(7, 0), Neighbourhood::Four, |_| true)
+            .is_none());
+    }
+
+    #[test]
+    fn connected_components() {
+        let size = Size::new(7, 4);
+        let four = size.connected_components(Neighbourhood::Four, wall);
+        assert_eq!(four.len(), 5);
+        assert_eq!(four.label(Coord::new(1, 0)), Some(0));
+        assert_eq!(four.label(Coord::new(6, 1)), Some(1));
+        assert_eq!(four.label(Coord::new(3, 3)), None);
+        assert_eq!(four.largest().unwrap().0, 0);
+        let eight = size.connected_components(Neighbourhood::Eight, wall);
+        assert_eq!(eight.len(), 3);
+        assert_eq!(eight.cells(0).count(), 5);
+        assert_eq!(
+            eight.components()[1],
+            super::Component {
+                top_left: Coord::new(6, 0),
+                size: Size::new(1, 2),
+                count: 2,
+            }
+        );
+        let open = size.connected_components(Neighbourhood::Four, |coord| !wall(coord));
+        assert_eq!(open.len(), 2);
+        assert_eq!(open.largest().unwrap().1.count, 19);
+        assert!(Size::new(0, 0)
+            .connected_components(Neighbourhood::Eight, |_| true)
+            .is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+//! Conversions between `Coord`/`Size` and the integer vector types of other crates, each
+//! behind a feature named after the crate. Conversions into `Size` are `TryFrom` since
+//! dimensions greater than `MAX_SIZE_FIELD` are rejected.
+
+#[cfg(feature = "glam")]
+mod with_glam {
+    use crate::coord::{Coord, Size};
+    use crate::error::DimensionTooLargeForSize;
+    use glam::{IVec2, UVec2};
+
+    impl From<Coord> for IVec2 {
+        fn from(Coord { x, y }: Coord) -> Self {
+            IVec2::new(x, y)
+        }
+    }
+
+    impl From<IVec2> for Coord {
+        fn from(v: IVec2) -> Self {
+            Coord::new(v.x, v.y)
+        }
+    }
+
+    impl From<Size> for UVec2 {
+        fn from(size: Size) -> Self {
+            UVec2::new(size.width(), size.height())
+        }
+    }
+
+    impl TryFrom<UVec2> for Size {
+        type Error = DimensionTooLargeForSize;
+        fn try_from(v: UVec2) -> Result<Self, Self::Error> {
+            Size::try_new(v.x, v.y)
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+mod with_mint {
+    use crate::coord::{Coord, Size};
+    use crate::error::DimensionTooLargeForSize;
+    use mint::{Point2, Vector2};
+
+    impl From<Coord> for Vector2<i32> {
+        fn from(Coord { x, y }: Coord) -> Self {
+            Vector2 { x, y }
+        }
+    }
+
+    impl From<Vector2<i32>> for Coord {
+        fn from(Vector2 { x, y }: Vector2<i32>) -> Self {
+            Coord::new(x, y)
+        }
+    }
+
+    impl From<Coord> for Point2<i32> {
+        fn from(Coord { x, y }: Coord) -> Self {
+            Point2 { x, y }
+        }
+    }
+
+    impl From<Point2<i32>> for Coord {
+        fn from(Point2 { x, y }: Point2<i32>) -> Self {
+            Coord::new(x, y)
+        }
+    }
+
+    impl From<Size> for Vector2<u32> {
+        fn from(size: Size) -> Self {
+            Vector2 {
+                x: size.width(),
+                y: size.height(),
+            }
+        }
+    }
+
+    impl TryFrom<Vector2<u32>> for Size {
+        type Error = DimensionTooLargeForSize;
+        fn try_from(Vector2 { x, y }: Vector2<u32>) -> Result<Self, Self::Error> {
+            Size::try_new(x, y)
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod with_nalgebra {
+    use crate::coord::{Coord, Size};
+    use crate::error::DimensionTooLargeForSize;
+    use nalgebra::{Point2, Vector2};
+
+    impl From<Coord> for Vector2<i32> {
+        fn from(Coord { x, y }: Coord) -> Self {
+            Vector2::new(x, y)
+        }
+    }
+
+    impl From<Vector2<i32>> for Coord {
+        fn from(v: Vector2<i32>) -> Self {
+            Coord::new(v.x, v.y)
+        }
+    }
+
+    impl From<Coord> for Point2<i32> {
+        fn from(Coord { x, y }: Coord) -> Self {
+            Point2::new(x, y)
+        }
+    }
+
+    impl From<Point2<i32>> for Coord {
+        fn from(p: Point2<i32>) -> Self {
+            Coord::new(p.x, p.y)
+        }
+    }
+
+    impl From<Size> for Vector2<u32> {
+        fn from(size: Size) -> Self {
+            Vector2::new(size.width(), size.height())
+        }
+    }
+
+    impl TryFrom<Vector2<u32>> for Size {
+        type Error = DimensionTooLargeForSize;
+        fn try_from(v: Vector2<u32>) -> Result<Self, Self::Error> {
+            Size::try_new(v.x, v.y)
+        }
+    }
+}
+
+#[cfg(feature = "euclid")]
+mod with_euclid {
+    use crate::coord::{Coord, Size};
+    use crate::error::DimensionTooLargeForSize;
+    use euclid::{Point2D, Size2D, Vector2D};
+
+    impl<U> From<Coord> for Point2D<i32, U> {
+        fn from(Coord { x, y }: Coord) -> Self {
+            Point2D::new(x, y)
+        }
+    }
+
+    impl<U> From<Point2D<i32, U>> for Coord {
+        fn from(p: Point2D<i32, U>) -> Self {
+            Coord::new(p.x, p.y)
+        }
+    }
+
+    impl<U> From<Coord> for Vector2D<i32, U> {
+        fn from(Coord { x, y }: Coord) -> Self {
+            Vector2D::new(x, y)
+        }
+    }
+
+    impl<U> From<Vector2D<i32, U>> for Coord {
+        fn from(v: Vector2D<i32, U>) -> Self {
+            Coord::new(v.x, v.y)
+        }
+    }
+
+    impl<U> From<Size> for Size2D<u32, U> {
+        fn from(size: Size) -> Self {
+            Size2D::new(size.width(), size.height())
+        }
+    }
+
+    impl<U> TryFrom<Size2D<u32, U>> for Size {
+        type Error = DimensionTooLargeForSize;
+        fn try_from(size: Size2D<u32, U>) -> Result<Self, Self::Error> {
+            Size::try_new(size.width, size.height)
+        }
+    }
+}
+
+#[cfg(feature = "cgmath")]
+mod with_cgmath {
+    use crate::coord::{Coord, Size};
+    use crate::error::DimensionTooLargeForSize;
+    use cgmath::{Point2, Vector2};
+
+    impl From<Coord> for Vector2<i32> {
+        fn from(Coord { x, y }: Coord) -> Self {
+            Vector2::new(x, y)
+        }
+    }
+
+    impl From<Vector2<i32>> for Coord {
+        fn from(v: Vector2<i32>) -> Self {
+            Coord::new(v.x, v.y)
+        }
+    }
+
+    impl From<Coord> for Point2<i32> {
+        fn from(Coord { x, y }: Coord) -> Self {
+            Point2::new(x, y)
+        }
+    }
+
+    impl From<Point2<i32>> for Coord {
+        fn from(p: Point2<i32>) -> Self {
+            Coord::new(p.x, p.y)
+        }
+    }
+
+    impl From<Size> for Vector2<u32> {
+        fn from(size: Size) -> Self {
+            Vector2::new(size.width(), size.height())
+        }
+    }
+
+    impl TryFrom<Vector2<u32>> for Size {
+        type Error = DimensionTooLargeForSize;
+        fn try_from(v: Vector2<u32>) -> Result<Self, Self::Error> {
+            Size::try_new(v.x, v.y)
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "glam",
+        feature = "mint",
+        feature = "nalgebra",
+        feature = "euclid",
+        feature = "cgmath"
+    )
+))]
+mod test {
+    use crate::coord::{Coord, Size};
+
+    #[test]
+    #[cfg(feature = "glam")]
+    fn glam() {
+        let v: glam::IVec2 = Coord::new(3, -4).into();
+        assert_eq!(Coord::from(v), Coord::new(3, -4));
+        let v: glam::UVec2 = Size::new(80, 24).into();
+        assert_eq!(Size::try_from(v), Ok(Size::new(80, 24)));
+        assert!(Size::try_from(glam::UVec2::new(u32::MAX, 0)).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "mint")]
+    fn mint() {
+        let v: mint::Vector2<i32> = Coord::new(3, -4).into();
+        assert_eq!(Coord::from(v), Coord::new(3, -4));
+        let p: mint::Point2<i32> = Coord::new(3, -4).into();
+        assert_eq!(Coord::from(p), Coord::new(3, -4));
+        let v: mint::Vector2<u32> = Size::new(80, 24).into();
+        assert_eq!(Size::try_from(v), Ok(Size::new(80, 24)));
+    }
+
+    #[test]
+    #[cfg(feature = "nalgebra")]
+    fn nalgebra() {
+        let v: nalgebra::Vector2<i32> = Coord::new(3, -4).into();
+        assert_eq!(Coord::from(v), Coord::new(3, -4));
+        let p: nalgebra::Point2<i32> = Coord::new(3, -4).into();
+        assert_eq!(Coord::from(p), Coord::new(3, -4));
+        let v: nalgebra::Vector2<u32> = Size::new(80, 24).into();
+        assert_eq!(Size::try_from(v), Ok(Size::new(80, 24)));
+    }
+
+    #[test]
+    #[cfg(feature = "euclid")]
+    fn euclid() {
+        let p: euclid::default::Point2D<i32> = Coord::new(3, -4).into();
+        assert_eq!(Coord::from(p), Coord::new(3, -4));
+        let v: euclid::default::Vector2D<i32> = Coord::new(3, -4).into();
+        assert_eq!(Coord::from(v), Coord::new(3, -4));
+        let s: euclid::default::Size2D<u32> = Size::new(80, 24).into();
+        assert_eq!(Size::try_from(s), Ok(Size::new(80, 24)));
+    }
+
+    #[test]
+    #[cfg(feature = "cgmath")]
+    fn cgmath() {
+        let v: cgmath::Vector2<i32> = Coord::new(3, -4).into();
+        assert_eq!(Coord::from(v), Coord::new(3, -4));
+        let p: cgmath::Point2<i32> = Coord::new(3, -4).into();
+        assert_eq!(Coord::from(p), Coord::new(3, -4));
+        let v: cgmath::Vector2<u32> = Size::new(80, 24).into();
+        assert_eq!(Size::try_from(v), Ok(Size::new(80, 24)));
+    }
+}
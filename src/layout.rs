@@ -0,0 +1,271 @@
+//! Flexbox-style division of a length, `Span` or rectangle into consecutive children along
+//! an axis.
+//!
+//! Each child has a `Constraint`. Space is allocated in two passes:
+//!
+//! 1. Every child is given its minimum length (the length of `Fixed` and `Min` children),
+//!    in order. If there isn't enough space for all of them, later children are truncated.
+//! 2. The remaining space is shared among the children which can grow, in proportion to
+//!    their weights. A child which reaches its maximum stops growing and its share is
+//!    redistributed among the others. Space left over from rounding goes to the earliest
+//!    children which can still grow.
+//!
+//! Results are written to a caller-provided slice so no allocation is needed.
+//!
+//! ```
+//! use coord_2d::{layout::{self, Constraint}, Axis, Coord, Size};
+//!
+//! // A sidebar of 20 columns, and a main pane and log pane sharing the rest 2:1.
+//! let constraints = [
+//!     Constraint::Fixed(20),
+//!     Constraint::Proportional(2),
+//!     Constraint::Proportional(1),
+//! ];
+//! let mut panes = [(Coord::new(0, 0), Size::new(0, 0)); 3];
+//! layout::split(Coord::new(0, 0), Size::new(80, 24), Axis::X, &constraints, &mut panes);
+//! assert_eq!(panes[0], (Coord::new(0, 0), Size::new(20, 24)));
+//! assert_eq!(panes[1], (Coord::new(20, 0), Size::new(40, 24)));
+//! assert_eq!(panes[2], (Coord::new(60, 0), Size::new(20, 24)));
+//! ```
+
+use crate::coord::{static_axis, Axis, Coord, Size, StaticAxis};
+use crate::span::Span;
+
+/// How the length of a child along the axis being divided is chosen.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Constraint {
+    /// Exactly this length.
+    Fixed(u32),
+    /// A share of the space left after minimum lengths are allocated, proportional to this
+    /// weight.
+    Proportional(u32),
+    /// At least this length, growing like `Fill` beyond it.
+    Min(u32),
+    /// Grows like `Fill`, but no longer than this length.
+    Max(u32),
+    /// An equal share of the remaining space. The same as `Proportional(1)`.
+    Fill,
+}
+
+impl Constraint {
+    const fn min(self) -> u32 {
+        match self {
+            Self::Fixed(length) | Self::Min(length) => length,
+            Self::Proportional(_) | Self::Max(_) | Self::Fill => 0,
+        }
+    }
+
+    const fn max(self) -> u32 {
+        match self {
+            Self::Fixed(length) | Self::Max(length) => length,
+            Self::Proportional(_) | Self::Min(_) | Self::Fill => u32::MAX,
+        }
+    }
+
+    const fn weight(self) -> u32 {
+        match self {
+            Self::Fixed(_) => 0,
+            Self::Proportional(weight) => weight,
+            Self::Min(_) | Self::Max(_) | Self::Fill => 1,
+        }
+    }
+}
+
+/// Divides `total` among `out` according to `constraints`, where the length of each child
+/// is stored in an element of `out` and accessed with `length` and `set_length`.
+fn distribute<T>(
+    total: u32,
+    constraints: &[Constraint],
+    out: &mut [T],
+    length: impl Fn(&T) -> u32,
+    set_length: impl Fn(&mut T, u32),
+) -> u32 {
+    assert_eq!(
+        constraints.len(),
+        out.len(),
+        "constraints and output have different lengths"
+    );
+    let mut remaining = total;
+    for (constraint, child) in constraints.iter().zip(out.iter_mut()) {
+        let min = constraint.min().min(remaining);
+        set_length(child, min);
+        remaining -= min;
+    }
+    let can_grow =
+        |constraint: &Constraint, length: u32| constraint.weight() > 0 && length < constraint.max();
+    'distribute: while remaining > 0 {
+        let total_weight = constraints
+            .iter()
+            .zip(out.iter())
+            .filter(|&(c, child)| can_grow(c, length(child)))
+            .map(|(c, _)| c.weight() as u64)
+            .sum::<u64>();
+        if total_weight == 0 {
+            break;
+        }
+        let share = |c: &Constraint| (remaining as u64 * c.weight() as u64 / total_weight) as u32;
+        // Cap one child at a time then recompute the shares, as the space a capped child
+        // doesn't use goes to the others.
+        for (constraint, child) in constraints.iter().zip(out.iter_mut()) {
+            let current = length(child);
+            if can_grow(constraint, current)
+                && current as u64 + share(constraint) as u64 >= constraint.max() as u64
+            {
+                remaining -= constraint.max() - current;
+                set_length(child, constraint.max());
+                continue 'distribute;
+            }
+        }
+        // No child reaches its maximum, so after taking its share each can take one more
+        // unit of the space lost to rounding, of which there's less than one unit per child.
+        let mut rounding = remaining;
+        for (constraint, child) in constraints.iter().zip(out.iter_mut()) {
+            let current = length(child);
+            if can_grow(constraint, current) {
+                rounding -= share(constraint);
+                set_length(child, current + share(constraint));
+            }
+        }
+        for (constraint, child) in constraints.iter().zip(out.iter_mut()) {
+            if rounding == 0 {
+                break;
+            }
+            if constraint.weight() > 0 && length(child) < constraint.max() {
+                set_length(child, length(child) + 1);
+                rounding -= 1;
+            }
+        }
+        remaining = 0;
+    }
+    remaining
+}
+
+/// Divides `total` according to `constraints`, writing the length of each child to `out`.
+/// Returns the space left unallocated, which is only non-zero if no child can grow to take
+/// it. Panics if `constraints` and `out` have different lengths.
+pub fn lengths(total: u32, constraints: &[Constraint], out: &mut [u32]) -> u32 {
+    distribute(
+        total,
+        constraints,
+        out,
+        |&length| length,
+        |l, length| *l = length,
+    )
+}
+
+/// Divides `span` into consecutive spans according to `constraints`, writing them to `out`.
+/// Returns the space left unallocated after the last child.
+/// Panics if `constraints` and `out` have different lengths.
+pub fn spans(span: Span, constraints: &[Constraint], out: &mut [Span]) -> u32 {
+    // Starting at `i32::MIN` every `u32` length can be stored in a span without saturating.
+    let remaining = distribute(
+        span.len(),
+        constraints,
+        out,
+        |child| child.len(),
+        |child, length| *child = Span::with_len(i32::MIN, length),
+    );
+    let mut start = span.start;
+    for child in out.iter_mut() {
+        *child = Span::with_len(start, child.len());
+        start = child.end;
+    }
+    remaining
+}
+
+/// Divides the rectangle with top-left corner `top_left` and dimensions `size` into
+/// consecutive rectangles along `A` according to `constraints`, writing the top-left corner
+/// and dimensions of each to `out`. Each child has the full extent of the rectangle along the
+/// other axis. Returns the space left unallocated after the last child.
+/// Panics if `constraints` and `out` have different lengths.
+pub fn split_static<A: StaticAxis>(
+    top_left: Coord,
+    size: Size,
+    constraints: &[Constraint],
+    out: &mut [(Coord, Size)],
+) -> u32 {
+    let remaining = distribute(
+        A::size_get(size),
+        constraints,
+        out,
+        |(_, child)| A::size_get(*child),
+        |(_, child), length| *child = A::size_set(size, length),
+    );
+    let mut start = A::coord_get(top_left);
+    for (child_top_left, child_size) in out.iter_mut() {
+        *child_top_left = A::coord_set(top_left, start);
+        start = Span::with_len(start, A::size_get(*child_size)).end;
+    }
+    remaining
+}
+
+/// Divides the rectangle with top-left corner `top_left` and dimensions `size` into
+/// consecutive rectangles along `axis`. See `split_static`.
+pub fn split(
+    top_left: Coord,
+    size: Size,
+    axis: Axis,
+    constraints: &[Constraint],
+    out: &mut [(Coord, Size)],
+) -> u32 {
+    match axis {
+        Axis::X => split_static::<static_axis::X>(top_left, size, constraints, out),
+        Axis::Y => split_static::<static_axis::Y>(top_left, size, constraints, out),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Constraint::*, *};
+
+    fn lengths_of<const N: usize>(total: u32, constraints: [Constraint; N]) -> ([u32; N], u32) {
+        let mut out = [0; N];
+        let remaining = lengths(total, &constraints, &mut out);
+        (out, remaining)
+    }
+
+    #[test]
+    fn constraints() {
+        assert_eq!(lengths_of(10, [Fixed(3), Fill]), ([3, 7], 0));
+        assert_eq!(lengths_of(10, [Fixed(3), Fixed(4)]), ([3, 4], 3));
+        assert_eq!(lengths_of(5, [Fixed(3), Fixed(4), Fill]), ([3, 2, 0], 0));
+        assert_eq!(lengths_of(10, [Fill, Fill, Fill]), ([4, 3, 3], 0));
+        assert_eq!(
+            lengths_of(12, [Proportional(1), Proportional(3)]),
+            ([3, 9], 0)
+        );
+        assert_eq!(lengths_of(10, [Max(2), Fill]), ([2, 8], 0));
+        assert_eq!(lengths_of(10, [Max(2), Max(3)]), ([2, 3], 5));
+        assert_eq!(lengths_of(10, [Min(8), Fill]), ([9, 1], 0));
+        assert_eq!(
+            lengths_of(10, [Min(4), Max(2), Proportional(0)]),
+            ([8, 2, 0], 0)
+        );
+        assert_eq!(lengths_of(0, [Min(4), Fill]), ([0, 0], 0));
+        assert_eq!(
+            lengths_of(u32::MAX, [Fill, Fixed(1)]),
+            ([u32::MAX - 1, 1], 0)
+        );
+        assert_eq!(lengths_of(7, []), ([], 7));
+    }
+
+    #[test]
+    fn rectangles() {
+        let constraints = [Fixed(2), Fill, Max(3)];
+        let mut out = [Span::empty(); 3];
+        assert_eq!(spans(Span::new(-5, 5), &constraints, &mut out), 0);
+        assert_eq!(out, [Span::new(-5, -3), Span::new(-3, 2), Span::new(2, 5)]);
+        let mut out = [(Coord::new(0, 0), Size::new(0, 0)); 3];
+        let top_left = Coord::new(1, 2);
+        let remaining = split(top_left, Size::new(4, 10), Axis::Y, &constraints, &mut out);
+        assert_eq!(remaining, 0);
+        assert_eq!(
+            out,
+            [
+                (Coord::new(1, 2), Size::new(4, 2)),
+                (Coord::new(1, 4), Size::new(4, 5)),
+                (Coord::new(1, 9), Size::new(4, 3)),
+            ]
+        );
+    }
+}
@@ -1,6 +1,76 @@
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod arbitrary_impls;
+mod axis_generic;
+pub mod binary;
+#[cfg(feature = "alloc")]
+mod bsp;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impls;
+#[cfg(feature = "alloc")]
+mod cellular_automaton;
+mod chunk;
 mod coord;
 mod coord_arithmetic;
+#[cfg(feature = "alloc")]
+mod coord_bit_set;
+#[cfg(feature = "alloc")]
+mod coord_map;
+mod error;
+mod fit;
+mod fixed_coord;
+#[cfg(feature = "alloc")]
+mod flood_fill;
+mod interop;
+pub mod layout;
+mod metric;
+mod neighbourhood;
+#[cfg(feature = "alloc")]
+mod quadtree;
+mod rule;
+#[cfg(feature = "serialize")]
+pub mod serde_repr;
+mod span;
+#[cfg(feature = "alloc")]
+mod spatial_hash;
+#[cfg(test)]
+mod test_strategies;
+mod text;
+mod viewport;
 
+#[cfg(feature = "arbitrary")]
+pub use arbitrary_impls::with_arbitrary as arbitrary;
+#[cfg(feature = "proptest")]
+pub use arbitrary_impls::with_proptest as proptest;
+#[cfg(feature = "quickcheck")]
+pub use arbitrary_impls::with_quickcheck as quickcheck;
+pub use axis_generic::{AxisRange, Line, Lines};
+#[cfg(all(feature = "alloc", feature = "rand"))]
+pub use bsp::SplitAxis;
+#[cfg(feature = "alloc")]
+pub use bsp::{Bsp, BspConfig, BspNode, BspSplit, Corridor};
+#[cfg(feature = "alloc")]
+pub use cellular_automaton::{CellNeighbours, CellularAutomaton, Edge};
+pub use chunk::{ChunkCoord, ChunkGrid, ChunksOverlapping};
 pub use coord::*;
+#[cfg(feature = "alloc")]
+pub use coord_bit_set::{CoordBitSet, Iter as CoordBitSetIter};
+#[cfg(feature = "alloc")]
+pub use coord_map::{CoordMap, CoordSet, Iter as CoordMapIter, Region as CoordMapRegion};
+pub use error::*;
+pub use fit::Align;
+pub use fixed_coord::FixedCoord;
+#[cfg(feature = "alloc")]
+pub use flood_fill::{Component, Components, FilledRegion};
+pub use metric::Metric;
+pub use neighbourhood::{Neighbourhood, Neighbours};
+#[cfg(feature = "alloc")]
+pub use quadtree::{Quadtree, Query as QuadtreeQuery};
+pub use rule::Rule;
+pub use span::Span;
+#[cfg(feature = "alloc")]
+pub use spatial_hash::{Query as SpatialHashQuery, SpatialHash};
+pub use viewport::{Viewport, ViewportCell, VisibleCells};
@@ -0,0 +1,81 @@
+//! Ways of measuring the distance between coordinates.
+
+use crate::coord::Coord;
+
+/// A way of measuring the distance between two coordinates.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Metric {
+    /// The sum of the differences along each axis, so the cells within a radius form a
+    /// diamond.
+    Manhattan,
+    /// The larger of the differences along each axis, so the cells within a radius form a
+    /// square.
+    Chebyshev,
+    /// The straight-line distance, so the cells within a radius form a circle.
+    Euclidean,
+}
+
+impl Metric {
+    /// The distance covering an offset of `dx` cells horizontally and `dy` cells vertically.
+    /// Euclidean distances are squared, which keeps them exact without changing their
+    /// ordering, and saturate at `u64::MAX`.
+    pub(crate) const fn of_offset(self, dx: u64, dy: u64) -> u64 {
+        match self {
+            Self::Manhattan => dx + dy,
+            Self::Chebyshev => {
+                if dx > dy {
+                    dx
+                } else {
+                    dy
+                }
+            }
+            Self::Euclidean => (dx * dx).saturating_add(dy * dy),
+        }
+    }
+
+    /// A value which can be compared with the result of `distance` to check whether it is at
+    /// most `radius`.
+    pub const fn of_radius(self, radius: u32) -> u64 {
+        match self {
+            Self::Manhattan | Self::Chebyshev => radius as u64,
+            Self::Euclidean => radius as u64 * radius as u64,
+        }
+    }
+
+    /// The distance between `a` and `b`. Euclidean distances are squared, which keeps them
+    /// exact without changing their ordering; use `of_radius` to get a value to compare them
+    /// against.
+    pub const fn distance(self, a: Coord, b: Coord) -> u64 {
+        self.of_offset(a.x.abs_diff(b.x) as u64, a.y.abs_diff(b.y) as u64)
+    }
+
+    /// Returns true if `a` and `b` are at most `radius` apart.
+    pub const fn is_within(self, a: Coord, b: Coord, radius: u32) -> bool {
+        self.distance(a, b) <= self.of_radius(radius)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Metric;
+    use crate::coord::Coord;
+
+    #[test]
+    fn distances() {
+        let a = Coord::new(1, 2);
+        let b = Coord::new(-2, 6);
+        assert_eq!(Metric::Manhattan.distance(a, b), 7);
+        assert_eq!(Metric::Chebyshev.distance(a, b), 4);
+        assert_eq!(Metric::Euclidean.distance(a, b), 25);
+        assert!(Metric::Euclidean.is_within(a, b, 5));
+        assert!(!Metric::Euclidean.is_within(a, b, 4));
+        assert!(!Metric::Manhattan.is_within(a, b, 6));
+        assert_eq!(
+            Metric::Euclidean.distance(
+                Coord::new(i32::MIN, i32::MIN),
+                Coord::new(i32::MAX, i32::MAX)
+            ),
+            u64::MAX
+        );
+    }
+}
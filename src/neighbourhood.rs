@@ -0,0 +1,100 @@
+//! The sets of cells considered adjacent to a cell.
+
+use crate::coord::Coord;
+use core::slice;
+
+/// Which cells are adjacent to a cell.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The 4 cells sharing an edge with the cell, also known as the von Neumann neighbourhood
+    /// or 4-connectivity.
+    Four,
+    /// The 8 cells sharing an edge or a corner with the cell, also known as the Moore
+    /// neighbourhood or 8-connectivity.
+    Eight,
+}
+
+const FOUR: [Coord; 4] = [
+    Coord::new(0, -1),
+    Coord::new(1, 0),
+    Coord::new(0, 1),
+    Coord::new(-1, 0),
+];
+
+const EIGHT: [Coord; 8] = [
+    Coord::new(0, -1),
+    Coord::new(1, -1),
+    Coord::new(1, 0),
+    Coord::new(1, 1),
+    Coord::new(0, 1),
+    Coord::new(-1, 1),
+    Coord::new(-1, 0),
+    Coord::new(-1, -1),
+];
+
+impl Neighbourhood {
+    /// The offsets from a cell to each of its neighbours, clockwise starting from the one
+    /// above it.
+    pub const fn offsets(self) -> &'static [Coord] {
+        match self {
+            Self::Four => &FOUR,
+            Self::Eight => &EIGHT,
+        }
+    }
+}
+
+impl Coord {
+    /// Iterates over the neighbours of this coordinate in `neighbourhood`, clockwise starting
+    /// from the one above it. Neighbours which can't be represented by a `Coord` are skipped.
+    pub fn neighbours(self, neighbourhood: Neighbourhood) -> Neighbours {
+        Neighbours {
+            coord: self,
+            offsets: neighbourhood.offsets().iter(),
+        }
+    }
+}
+
+/// Iterator over the neighbours of a coordinate, returned by `Coord::neighbours`.
+#[derive(Debug, Clone)]
+pub struct Neighbours {
+    coord: Coord,
+    offsets: slice::Iter<'static, Coord>,
+}
+
+impl Iterator for Neighbours {
+    type Item = Coord;
+    fn next(&mut self) -> Option<Self::Item> {
+        let coord = self.coord;
+        self.offsets
+            .by_ref()
+            .find_map(|&offset| coord.checked_add(offset))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Neighbourhood;
+    use crate::coord::Coord;
+
+    #[test]
+    fn neighbours() {
+        assert_eq!(
+            Coord::new(2, 2)
+                .neighbours(Neighbourhood::Four)
+                .collect::<Vec<_>>(),
+            [
+                Coord::new(2, 1),
+                Coord::new(3, 2),
+                Coord::new(2, 3),
+                Coord::new(1, 2)
+            ]
+        );
+        assert_eq!(Coord::new(0, 0).neighbours(Neighbourhood::Eight).count(), 8);
+        assert_eq!(
+            Coord::new(i32::MAX, i32::MIN)
+                .neighbours(Neighbourhood::Eight)
+                .count(),
+            3
+        );
+    }
+}
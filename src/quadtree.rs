@@ -0,0 +1,517 @@
+//! A spatial index of values at points within a `Size`.
+//!
+//! Each node covers a rectangle of cells and stores its entries directly until it holds more
+//! than `LEAF_CAPACITY` of them, at which point it splits into four quadrants. Queries only
+//! visit the nodes whose rectangles could contain a match, so answering "what's within N
+//! cells of here" touches a number of entries close to the number of results rather than the
+//! number of entries in the tree.
+
+use crate::coord::{Coord, Size};
+use crate::metric::Metric;
+use alloc::{boxed::Box, vec::Vec};
+use core::slice;
+
+/// The number of entries a node holds before it splits into quadrants.
+const LEAF_CAPACITY: usize = 8;
+
+/// A rectangle of cells in the tree. All cells in a tree have non-negative coordinates.
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Bounds {
+    /// The quadrants of the rectangle, ordered top-left, top-right, bottom-left,
+    /// bottom-right. When a dimension is odd the left or top quadrants are larger, so with a
+    /// dimension of 1 the right or bottom quadrants are empty.
+    fn quadrants(self) -> [Self; 4] {
+        let (width, height) = (self.width.div_ceil(2), self.height.div_ceil(2));
+        let (x, y) = (self.x + width, self.y + height);
+        let (right, bottom) = (self.width - width, self.height - height);
+        [
+            Self {
+                width,
+                height,
+                ..self
+            },
+            Self {
+                x,
+                width: right,
+                height,
+                ..self
+            },
+            Self {
+                y,
+                width,
+                height: bottom,
+                ..self
+            },
+            Self {
+                x,
+                y,
+                width: right,
+                height: bottom,
+            },
+        ]
+    }
+
+    /// The index in `quadrants` of the quadrant containing `coord`.
+    fn quadrant_of(self, coord: Coord) -> usize {
+        let right = coord.x as u32 >= self.x + self.width.div_ceil(2);
+        let bottom = coord.y as u32 >= self.y + self.height.div_ceil(2);
+        right as usize | (bottom as usize) << 1
+    }
+
+    fn is_splittable(self) -> bool {
+        self.width > 1 || self.height > 1
+    }
+
+    /// The distances along each axis from `coord` to the nearest cell of the rectangle.
+    fn offset_from(self, coord: Coord) -> (u64, u64) {
+        fn axis(position: i32, start: u32, len: u32) -> u64 {
+            let (position, start) = (position as i64, start as i64);
+            let last = start + len as i64 - 1;
+            if position < start {
+                (start - position) as u64
+            } else if position > last {
+                (position - last) as u64
+            } else {
+                0
+            }
+        }
+        (
+            axis(coord.x, self.x, self.width),
+            axis(coord.y, self.y, self.height),
+        )
+    }
+}
+
+/// Which entries a query yields.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Filter {
+    All,
+    /// Cells with `x0 <= x < x1` and `y0 <= y < y1`.
+    Rect {
+        x0: i64,
+        y0: i64,
+        x1: i64,
+        y1: i64,
+    },
+    Within {
+        centre: Coord,
+        metric: Metric,
+        radius: u32,
+    },
+}
+
+impl Filter {
+    /// Matches the cells of the rectangle with top-left corner `top_left` and dimensions
+    /// `size`.
+    pub(crate) fn rect(top_left: Coord, size: Size) -> Self {
+        let (x0, y0) = (top_left.x as i64, top_left.y as i64);
+        Self::Rect {
+            x0,
+            y0,
+            x1: x0 + size.width() as i64,
+            y1: y0 + size.height() as i64,
+        }
+    }
+
+    /// Matches the cells at most `radius` from `centre` under `metric`.
+    pub(crate) fn within(centre: Coord, radius: u32, metric: Metric) -> Self {
+        Self::Within {
+            centre,
+            metric,
+            radius,
+        }
+    }
+
+    fn may_contain(self, bounds: Bounds) -> bool {
+        match self {
+            Self::All => true,
+            Self::Rect { x0, y0, x1, y1 } => {
+                (bounds.x as i64) < x1
+                    && x0 < bounds.x as i64 + bounds.width as i64
+                    && (bounds.y as i64) < y1
+                    && y0 < bounds.y as i64 + bounds.height as i64
+            }
+            Self::Within {
+                centre,
+                metric,
+                radius,
+            } => {
+                let (dx, dy) = bounds.offset_from(centre);
+                metric.of_offset(dx, dy) <= metric.of_radius(radius)
+            }
+        }
+    }
+
+    pub(crate) fn contains(self, coord: Coord) -> bool {
+        match self {
+            Self::All => true,
+            Self::Rect { x0, y0, x1, y1 } => {
+                let (x, y) = (coord.x as i64, coord.y as i64);
+                x0 <= x && x < x1 && y0 <= y && y < y1
+            }
+            Self::Within {
+                centre,
+                metric,
+                radius,
+            } => metric.is_within(centre, coord, radius),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    /// The entries of a leaf. Nodes with children have no entries of their own.
+    entries: Vec<(Coord, T)>,
+    children: Option<Box<[Node<T>; 4]>>,
+}
+
+impl<T> Node<T> {
+    const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            children: None,
+        }
+    }
+
+    fn insert(&mut self, bounds: Bounds, coord: Coord, value: T) {
+        match &mut self.children {
+            Some(children) => {
+                let quadrant = bounds.quadrant_of(coord);
+                children[quadrant].insert(bounds.quadrants()[quadrant], coord, value);
+            }
+            None => {
+                self.entries.push((coord, value));
+                if self.entries.len() > LEAF_CAPACITY && bounds.is_splittable() {
+                    let mut children = Box::new([(); 4].map(|_| Self::new()));
+                    let quadrants = bounds.quadrants();
+                    for (coord, value) in self.entries.drain(..) {
+                        let quadrant = bounds.quadrant_of(coord);
+                        children[quadrant].insert(quadrants[quadrant], coord, value);
+                    }
+                    self.entries = Vec::new();
+                    self.children = Some(children);
+                }
+            }
+        }
+    }
+
+    fn remove<F: FnMut(&T) -> bool>(
+        &mut self,
+        bounds: Bounds,
+        coord: Coord,
+        f: &mut F,
+    ) -> Option<T> {
+        let Some(children) = &mut self.children else {
+            let index = self
+                .entries
+                .iter()
+                .position(|(entry_coord, value)| *entry_coord == coord && f(value))?;
+            return Some(self.entries.swap_remove(index).1);
+        };
+        let quadrant = bounds.quadrant_of(coord);
+        let removed = children[quadrant].remove(bounds.quadrants()[quadrant], coord, f)?;
+        // Merge the children back into this node once they would fit in a single leaf.
+        let mergeable = children.iter().all(|child| child.children.is_none())
+            && children
+                .iter()
+                .map(|child| child.entries.len())
+                .sum::<usize>()
+                <= LEAF_CAPACITY;
+        if mergeable {
+            let children = self.children.take().unwrap();
+            for child in *children {
+                self.entries.extend(child.entries);
+            }
+        }
+        Some(removed)
+    }
+
+    fn nearest<'a, F: FnMut(Coord, &T) -> bool>(
+        &'a self,
+        bounds: Bounds,
+        coord: Coord,
+        metric: Metric,
+        f: &mut F,
+        best: &mut Option<(u64, Coord, &'a T)>,
+    ) {
+        for (entry_coord, value) in &self.entries {
+            let distance = metric.distance(coord, *entry_coord);
+            if best.is_none_or(|(best, _, _)| distance < best) && f(*entry_coord, value) {
+                *best = Some((distance, *entry_coord, value));
+            }
+        }
+        if let Some(children) = &self.children {
+            // Visit the closest quadrants first so the rest are more likely to be pruned.
+            let quadrants = bounds.quadrants();
+            let mut order: [(u64, usize); 4] = core::array::from_fn(|i| {
+                let (dx, dy) = quadrants[i].offset_from(coord);
+                (metric.of_offset(dx, dy), i)
+            });
+            order.sort_unstable();
+            for (distance, i) in order {
+                if best.is_some_and(|(best, _, _)| distance >= best) {
+                    break;
+                }
+                children[i].nearest(quadrants[i], coord, metric, f, best);
+            }
+        }
+    }
+}
+
+/// A spatial index of values at points within a `Size`, supporting rectangle, radius and
+/// nearest-neighbour queries. Any number of values may be at the same point.
+#[derive(Debug, Clone)]
+pub struct Quadtree<T> {
+    size: Size,
+    len: usize,
+    root: Node<T>,
+}
+
+impl<T> Quadtree<T> {
+    /// An empty tree for points within `size`.
+    pub const fn new(size: Size) -> Self {
+        Self {
+            size,
+            len: 0,
+            root: Node::new(),
+        }
+    }
+
+    pub const fn size(&self) -> Size {
+        self.size
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = Node::new();
+        self.len = 0;
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds {
+            x: 0,
+            y: 0,
+            width: self.size.width(),
+            height: self.size.height(),
+        }
+    }
+
+    /// Panics if `coord` is outside the size of the tree.
+    pub fn insert(&mut self, coord: Coord, value: T) {
+        assert!(
+            coord.is_valid(self.size),
+            "{:?} is outside the size {:?}",
+            coord,
+            self.size
+        );
+        self.root.insert(self.bounds(), coord, value);
+        self.len += 1;
+    }
+
+    /// Removes and returns a value at `coord` equal to `value`.
+    pub fn remove(&mut self, coord: Coord, value: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        self.remove_where(coord, |entry| entry == value)
+    }
+
+    /// Removes and returns a value at `coord` for which `f` returns true.
+    pub fn remove_where<F: FnMut(&T) -> bool>(&mut self, coord: Coord, mut f: F) -> Option<T> {
+        if !coord.is_valid(self.size) {
+            return None;
+        }
+        let removed = self.root.remove(self.bounds(), coord, &mut f)?;
+        self.len -= 1;
+        Some(removed)
+    }
+
+    fn query(&self, filter: Filter) -> Query<'_, T> {
+        let mut stack = Vec::new();
+        if filter.may_contain(self.bounds()) {
+            stack.push((&self.root, self.bounds()));
+        }
+        Query {
+            filter,
+            stack,
+            entries: [].iter(),
+        }
+    }
+
+    /// Iterates over every entry in the tree, in no particular order.
+    pub fn iter(&self) -> Query<'_, T> {
+        self.query(Filter::All)
+    }
+
+    /// Iterates over the entries within the rectangle with top-left corner `top_left` and
+    /// dimensions `size`, in no particular order.
+    pub fn rect(&self, top_left: Coord, size: Size) -> Query<'_, T> {
+        self.query(Filter::rect(top_left, size))
+    }
+
+    /// Iterates over the entries at most `radius` from `centre` under `metric`, in no
+    /// particular order.
+    pub fn within(&self, centre: Coord, radius: u32, metric: Metric) -> Query<'_, T> {
+        self.query(Filter::within(centre, radius, metric))
+    }
+
+    /// The entry closest to `coord` under `metric`. Ties are broken arbitrarily.
+    pub fn nearest(&self, coord: Coord, metric: Metric) -> Option<(Coord, &T)> {
+        self.nearest_where(coord, metric, |_, _| true)
+    }
+
+    /// The entry closest to `coord` under `metric` for which `f` returns true, such as the
+    /// nearest entity other than the one at `coord`. Ties are broken arbitrarily.
+    pub fn nearest_where<F: FnMut(Coord, &T) -> bool>(
+        &self,
+        coord: Coord,
+        metric: Metric,
+        mut f: F,
+    ) -> Option<(Coord, &T)> {
+        let mut best = None;
+        self.root
+            .nearest(self.bounds(), coord, metric, &mut f, &mut best);
+        best.map(|(_, coord, value)| (coord, value))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Quadtree<T> {
+    type Item = (Coord, &'a T);
+    type IntoIter = Query<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a `Quadtree` matching a query.
+#[derive(Debug, Clone)]
+pub struct Query<'a, T> {
+    filter: Filter,
+    /// Nodes which may contain matching entries and haven't been visited yet.
+    stack: Vec<(&'a Node<T>, Bounds)>,
+    entries: slice::Iter<'a, (Coord, T)>,
+}
+
+impl<'a, T> Iterator for Query<'a, T> {
+    type Item = (Coord, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            for (coord, value) in self.entries.by_ref() {
+                if self.filter.contains(*coord) {
+                    return Some((*coord, value));
+                }
+            }
+            let (node, bounds) = self.stack.pop()?;
+            self.entries = node.entries.iter();
+            if let Some(children) = &node.children {
+                for (child, bounds) in children.iter().zip(bounds.quadrants()) {
+                    if self.filter.may_contain(bounds) {
+                        self.stack.push((child, bounds));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Quadtree;
+    use crate::coord::{Coord, Size};
+    use crate::metric::Metric;
+    use crate::test_strategies::{indices_in_rect, indices_within, metric};
+    use proptest::prelude::*;
+
+    #[test]
+    fn insert_remove() {
+        let mut tree = Quadtree::new(Size::new(64, 64));
+        for i in 0..100 {
+            tree.insert(Coord::new(i % 64, i / 10), i);
+        }
+        tree.insert(Coord::new(5, 5), 1000);
+        tree.insert(Coord::new(5, 5), 1001);
+        assert_eq!(tree.len(), 102);
+        assert_eq!(tree.remove(Coord::new(5, 5), &1001), Some(1001));
+        assert_eq!(tree.remove(Coord::new(5, 5), &1001), None);
+        assert_eq!(tree.remove(Coord::new(70, 5), &1000), None);
+        for i in 0..100 {
+            assert_eq!(tree.remove(Coord::new(i % 64, i / 10), &i), Some(i));
+        }
+        assert_eq!(tree.iter().collect::<Vec<_>>(), [(Coord::new(5, 5), &1000)]);
+        assert!(tree.root.children.is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        Quadtree::new(Size::new(4, 4)).insert(Coord::new(0, 4), ());
+    }
+
+    #[test]
+    fn many_at_one_point() {
+        let mut tree = Quadtree::new(Size::new(1, 1));
+        for i in 0..20 {
+            tree.insert(Coord::new(0, 0), i);
+        }
+        assert_eq!(
+            tree.within(Coord::new(3, 0), 3, Metric::Manhattan).count(),
+            20
+        );
+        assert_eq!(
+            tree.nearest(Coord::new(0, 0), Metric::Euclidean).unwrap().0,
+            Coord::new(0, 0)
+        );
+    }
+
+    fn entries() -> impl Strategy<Value = Vec<(i32, i32)>> {
+        proptest::collection::vec((0..50i32, 0..40i32), 0..200)
+    }
+
+    fn build(entries: &[(i32, i32)]) -> Quadtree<usize> {
+        let mut tree = Quadtree::new(Size::new(50, 40));
+        for (i, &(x, y)) in entries.iter().enumerate() {
+            tree.insert(Coord::new(x, y), i);
+        }
+        tree
+    }
+
+    proptest! {
+        #[test]
+        fn queries_match_brute_force(
+            entries in entries(),
+            (x, y, w, h) in (-10..60i32, -10..50i32, 0..30u32, 0..30u32),
+            radius in 0..20u32,
+            metric in metric(),
+        ) {
+            let tree = build(&entries);
+            let coords = entries.iter().map(|&entry| Coord::from(entry)).collect::<Vec<_>>();
+            let centre = Coord::new(x, y);
+            let mut rect = tree.rect(centre, Size::new(w, h)).map(|(_, &i)| i).collect::<Vec<_>>();
+            rect.sort();
+            prop_assert_eq!(rect, indices_in_rect(&coords, centre, Size::new(w, h)));
+            let mut within = tree.within(centre, radius, metric).map(|(_, &i)| i).collect::<Vec<_>>();
+            within.sort();
+            prop_assert_eq!(within, indices_within(&coords, centre, radius, metric));
+            let nearest = tree.nearest(centre, metric).map(|(coord, _)| metric.distance(centre, coord));
+            let expected = entries.iter().map(|&(ex, ey)| metric.distance(centre, Coord::new(ex, ey))).min();
+            prop_assert_eq!(nearest, expected);
+            let odd = tree.nearest_where(centre, metric, |_, &i| i % 2 == 1).map(|(coord, _)| metric.distance(centre, coord));
+            let expected = entries.iter().enumerate().filter(|(i, _)| i % 2 == 1).map(|(_, &(ex, ey))| metric.distance(centre, Coord::new(ex, ey))).min();
+            prop_assert_eq!(odd, expected);
+        }
+    }
+}
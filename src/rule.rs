@@ -0,0 +1,91 @@
+//! Birth/survival rules for cellular automata in the style of Conway's Game of Life.
+
+/// Rule deciding whether a cell is alive in the next generation based on whether it's alive
+/// now and how many of its neighbours are alive. Written as `B3/S23`, meaning a dead cell
+/// with 3 live neighbours is born, and a live cell with 2 or 3 live neighbours survives.
+///
+/// Neighbour counts range from 0 to 8, covering both 4- and 8-cell neighbourhoods.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub struct Rule {
+    /// Bit `n` is set if a dead cell with `n` live neighbours is born.
+    birth: u16,
+    /// Bit `n` is set if a live cell with `n` live neighbours survives.
+    survival: u16,
+}
+
+/// The largest number of neighbours a cell can have.
+pub(crate) const MAX_NEIGHBOURS: u8 = 8;
+
+const fn mask(counts: &[u8]) -> u16 {
+    let mut mask = 0;
+    let mut i = 0;
+    while i < counts.len() {
+        assert!(
+            counts[i] <= MAX_NEIGHBOURS,
+            "neighbour count is greater than 8"
+        );
+        mask |= 1 << counts[i];
+        i += 1;
+    }
+    mask
+}
+
+impl Rule {
+    /// Conway's Game of Life, `B3/S23`.
+    pub const LIFE: Self = Self::new(&[3], &[2, 3]);
+
+    /// A common rule for generating caves, `B678/S345678`.
+    pub const CAVE: Self = Self::new(&[6, 7, 8], &[3, 4, 5, 6, 7, 8]);
+
+    /// The rule where dead cells with any count in `birth` live neighbours are born, and live
+    /// cells with any count in `survival` live neighbours survive. Panics if a count is
+    /// greater than 8.
+    pub const fn new(birth: &[u8], survival: &[u8]) -> Self {
+        Self {
+            birth: mask(birth),
+            survival: mask(survival),
+        }
+    }
+
+    /// Returns true if a dead cell with `live_neighbours` live neighbours is born.
+    pub const fn is_birth(self, live_neighbours: u8) -> bool {
+        live_neighbours <= MAX_NEIGHBOURS && self.birth & (1 << live_neighbours) != 0
+    }
+
+    /// Returns true if a live cell with `live_neighbours` live neighbours survives.
+    pub const fn is_survival(self, live_neighbours: u8) -> bool {
+        live_neighbours <= MAX_NEIGHBOURS && self.survival & (1 << live_neighbours) != 0
+    }
+
+    /// Whether a cell is alive in the next generation.
+    pub const fn next(self, alive: bool, live_neighbours: u8) -> bool {
+        if alive {
+            self.is_survival(live_neighbours)
+        } else {
+            self.is_birth(live_neighbours)
+        }
+    }
+
+    pub(crate) const fn set(&mut self, birth: bool, live_neighbours: u8) {
+        if birth {
+            self.birth |= 1 << live_neighbours;
+        } else {
+            self.survival |= 1 << live_neighbours;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rule;
+
+    #[test]
+    fn life() {
+        assert!(Rule::LIFE.next(false, 3));
+        assert!(!Rule::LIFE.next(false, 2));
+        assert!(Rule::LIFE.next(true, 2));
+        assert!(!Rule::LIFE.next(true, 4));
+        assert!(!Rule::LIFE.next(true, 200));
+        assert_eq!(Rule::default(), Rule::new(&[], &[]));
+    }
+}
@@ -0,0 +1,188 @@
+//! Alternative serde representations of `Coord` and `Size`, for use with
+//! `#[serde(with = "...")]`.
+//!
+//! ```
+//! use coord_2d::{Coord, Size};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "coord_2d::serde_repr::string")]
+//!     window: Size, // "80x24"
+//!     #[serde(with = "coord_2d::serde_repr::tuple")]
+//!     origin: Coord, // [3, -4]
+//! }
+//! ```
+//!
+//! All representations validate `Size` dimensions against `MAX_SIZE_FIELD` when
+//! deserializing.
+
+use crate::coord::{Coord, Size};
+use crate::error::{DimensionTooLargeForSize, ParseError};
+use core::{fmt, str::FromStr};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The shape in which the default `Deserialize` implementation of `Size` reads its input
+/// before it is validated.
+#[derive(Deserialize)]
+#[serde(rename = "Size")]
+pub(crate) struct SizeFields {
+    x: u32,
+    y: u32,
+}
+
+impl TryFrom<SizeFields> for Size {
+    type Error = DimensionTooLargeForSize;
+    fn try_from(SizeFields { x, y }: SizeFields) -> Result<Self, Self::Error> {
+        Size::try_new(x, y)
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::Coord {}
+    impl Sealed for super::Size {}
+}
+
+/// Types which can be used with the representations in this module: `Coord` and `Size`.
+pub trait Components:
+    Copy + fmt::Display + FromStr<Err = ParseError> + Serialize + DeserializeOwned + private::Sealed
+{
+    type Component: Serialize + DeserializeOwned;
+    fn components(self) -> (Self::Component, Self::Component);
+    fn try_from_components(
+        x: Self::Component,
+        y: Self::Component,
+    ) -> Result<Self, DimensionTooLargeForSize>;
+}
+
+impl Components for Coord {
+    type Component = i32;
+    fn components(self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+    fn try_from_components(x: i32, y: i32) -> Result<Self, DimensionTooLargeForSize> {
+        Ok(Coord::new(x, y))
+    }
+}
+
+impl Components for Size {
+    type Component = u32;
+    fn components(self) -> (u32, u32) {
+        (self.width(), self.height())
+    }
+    fn try_from_components(x: u32, y: u32) -> Result<Self, DimensionTooLargeForSize> {
+        Size::try_new(x, y)
+    }
+}
+
+/// Represents a value as a 2-tuple, e.g. `[3, -4]` in JSON.
+pub mod tuple {
+    use super::Components;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T: Components, S: Serializer>(value: &T, s: S) -> Result<S::Ok, S::Error> {
+        value.components().serialize(s)
+    }
+
+    pub fn deserialize<'de, T: Components, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+        let (x, y) = <(T::Component, T::Component)>::deserialize(d)?;
+        T::try_from_components(x, y).map_err(D::Error::custom)
+    }
+}
+
+/// Represents a value as a string in the format of its `Display` and `FromStr`
+/// implementations, e.g. `"(3,-4)"` or `"80x24"`.
+pub mod string {
+    use super::Components;
+    use core::{fmt, marker::PhantomData};
+    use serde::{
+        de::{Error, Visitor},
+        Deserializer, Serializer,
+    };
+
+    pub fn serialize<T: Components, S: Serializer>(value: &T, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(value)
+    }
+
+    struct StrVisitor<T>(PhantomData<T>);
+
+    impl<T: Components> Visitor<'_> for StrVisitor<T> {
+        type Value = T;
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a string such as \"(3,-4)\" or \"80x24\"")
+        }
+        fn visit_str<E: Error>(self, v: &str) -> Result<T, E> {
+            v.parse().map_err(E::custom)
+        }
+    }
+
+    pub fn deserialize<'de, T: Components, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+        d.deserialize_str(StrVisitor(PhantomData))
+    }
+}
+
+/// Represents a value as a struct with fields `x` and `y`. This is the same as the default
+/// representation, and is provided so the choice can be made explicit.
+pub mod fields {
+    use super::Components;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<T: Components, S: Serializer>(value: &T, s: S) -> Result<S::Ok, S::Error> {
+        value.serialize(s)
+    }
+
+    pub fn deserialize<'de, T: Components, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+        T::deserialize(d)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::coord::{Coord, Size};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reprs {
+        #[serde(with = "super::tuple")]
+        tuple: Size,
+        #[serde(with = "super::string")]
+        string: Size,
+        #[serde(with = "super::string")]
+        coord: Coord,
+        #[serde(with = "super::fields")]
+        fields: Size,
+    }
+
+    #[test]
+    fn validates_size() {
+        assert_eq!(
+            serde_json::from_str::<Size>(r#"{"x":80,"y":24}"#).unwrap(),
+            Size::new(80, 24)
+        );
+        let e = serde_json::from_str::<Size>(r#"{"x":4294967295,"y":24}"#).unwrap_err();
+        assert!(e.to_string().contains("too large"));
+    }
+
+    #[test]
+    fn representations() {
+        let reprs = Reprs {
+            tuple: Size::new(1, 2),
+            string: Size::new(80, 24),
+            coord: Coord::new(3, -4),
+            fields: Size::new(5, 6),
+        };
+        let json = serde_json::to_string(&reprs).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tuple":[1,2],"string":"80x24","coord":"(3,-4)","fields":{"x":5,"y":6}}"#
+        );
+        assert_eq!(serde_json::from_str::<Reprs>(&json).unwrap(), reprs);
+        let bad =
+            r#"{"tuple":[4294967295,2],"string":"80x24","coord":"(3,-4)","fields":{"x":5,"y":6}}"#;
+        assert!(serde_json::from_str::<Reprs>(bad).is_err());
+        let bad = r#"{"tuple":[1,2],"string":"80y24","coord":"(3,-4)","fields":{"x":5,"y":6}}"#;
+        assert!(serde_json::from_str::<Reprs>(bad).is_err());
+    }
+}
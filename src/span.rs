@@ -0,0 +1,313 @@
+//! One-dimensional half-open intervals of positions, for working with a `Size` or a
+//! rectangle one axis at a time.
+
+use crate::axis_generic::AxisRange;
+use crate::coord::{Axis, Coord, Size, StaticAxis};
+use core::ops::Range;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// The half-open interval of positions `start..end`. A span whose end is not after its start
+/// is empty. `AxisRange` is a span tagged with the axis it lies along.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: i32,
+    pub end: i32,
+}
+
+/// Adds a dimension to a position, saturating at `i32::MAX` as positions beyond it can't be
+/// represented.
+const fn end_of(start: i32, len: u32) -> i32 {
+    start.saturating_add_unsigned(len)
+}
+
+const fn min(a: i32, b: i32) -> i32 {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+const fn max(a: i32, b: i32) -> i32 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+impl Span {
+    pub const fn new(start: i32, end: i32) -> Self {
+        Self { start, end }
+    }
+
+    /// The span of `len` positions beginning at `start`. The end saturates at `i32::MAX`.
+    pub const fn with_len(start: i32, len: u32) -> Self {
+        Self::new(start, end_of(start, len))
+    }
+
+    pub const fn empty() -> Self {
+        Self::new(0, 0)
+    }
+
+    pub const fn len(self) -> u32 {
+        if self.is_empty() {
+            0
+        } else {
+            self.end.abs_diff(self.start)
+        }
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.end <= self.start
+    }
+
+    pub const fn contains(self, position: i32) -> bool {
+        position >= self.start && position < self.end
+    }
+
+    /// Returns true if every position in `other` is in `self`. Empty spans are contained by
+    /// every span.
+    pub const fn contains_span(self, other: Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub const fn overlaps(self, other: Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The positions in both spans. If the spans don't overlap the result is empty.
+    pub const fn intersection(self, other: Self) -> Self {
+        let start = max(self.start, other.start);
+        Self::new(start, max(start, min(self.end, other.end)))
+    }
+
+    /// The positions in either span, or `None` if there's a gap between them so the result
+    /// can't be represented as a single span.
+    pub const fn union(self, other: Self) -> Option<Self> {
+        if self.is_empty()
+            || other.is_empty()
+            || (self.start <= other.end && other.start <= self.end)
+        {
+            Some(self.hull(other))
+        } else {
+            None
+        }
+    }
+
+    /// The smallest span containing both spans, including any gap between them. Empty spans
+    /// are ignored.
+    pub const fn hull(self, other: Self) -> Self {
+        if self.is_empty() {
+            other
+        } else if other.is_empty() {
+            self
+        } else {
+            Self::new(min(self.start, other.start), max(self.end, other.end))
+        }
+    }
+
+    /// The positions in `self` which aren't in `other`. Removing a span from the middle of
+    /// another leaves two pieces, so this returns the piece before `other` and the piece after
+    /// it, either of which may be empty.
+    pub const fn difference(self, other: Self) -> (Self, Self) {
+        if other.is_empty() {
+            return (self, Self::new(self.end, self.end));
+        }
+        let before = Self::new(self.start, max(self.start, min(self.end, other.start)));
+        let after_start = min(self.end, max(self.start, other.end));
+        (before, Self::new(after_start, max(after_start, self.end)))
+    }
+
+    /// Moves the span by `offset` without changing its length.
+    pub const fn shift(self, offset: i32) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+
+    /// The positions of the cells of `size` along `axis`, starting at 0.
+    pub const fn of_size(size: Size, axis: Axis) -> Self {
+        Self::with_len(0, size.get(axis))
+    }
+
+    /// The positions of the cells of `size` along `A`, starting at 0.
+    pub fn of_size_static<A: StaticAxis>(size: Size) -> Self {
+        Self::with_len(0, A::size_get(size))
+    }
+
+    /// Projects the rectangle with top-left corner `top_left` and dimensions `size` onto
+    /// `axis`.
+    pub const fn of_rect(top_left: Coord, size: Size, axis: Axis) -> Self {
+        Self::with_len(top_left.get(axis), size.get(axis))
+    }
+
+    /// Projects the rectangle with top-left corner `top_left` and dimensions `size` onto `A`.
+    pub fn of_rect_static<A: StaticAxis>(top_left: Coord, size: Size) -> Self {
+        Self::with_len(A::coord_get(top_left), A::size_get(size))
+    }
+
+    /// Combines a span along `axis` with a span along the other axis into a rectangle,
+    /// returned as its top-left corner and dimensions. Empty spans produce a dimension of 0.
+    /// Returns `None` if either span is longer than `MAX_SIZE_FIELD`.
+    pub const fn checked_to_rect(self, across: Self, axis: Axis) -> Option<(Coord, Size)> {
+        match axis.try_new_size(self.len(), across.len()) {
+            Ok(size) => Some((axis.new_coord(self.start, across.start), size)),
+            Err(_) => None,
+        }
+    }
+
+    /// Like `checked_to_rect`, but panics if either span is longer than `MAX_SIZE_FIELD`.
+    pub const fn to_rect(self, across: Self, axis: Axis) -> (Coord, Size) {
+        (
+            axis.new_coord(self.start, across.start),
+            axis.new_size(self.len(), across.len()),
+        )
+    }
+
+    /// Combines a span along `A` with a span along the other axis into a rectangle, returned
+    /// as its top-left corner and dimensions. Empty spans produce a dimension of 0.
+    /// Returns `None` if either span is longer than `MAX_SIZE_FIELD`.
+    pub fn checked_to_rect_static<A: StaticAxis>(self, across: Self) -> Option<(Coord, Size)> {
+        A::try_new_size(self.len(), across.len())
+            .ok()
+            .map(|size| (A::new_coord(self.start, across.start), size))
+    }
+
+    /// Like `checked_to_rect_static`, but panics if either span is longer than
+    /// `MAX_SIZE_FIELD`.
+    pub fn to_rect_static<A: StaticAxis>(self, across: Self) -> (Coord, Size) {
+        (
+            A::new_coord(self.start, across.start),
+            A::new_size(self.len(), across.len()),
+        )
+    }
+
+    /// This span as a range along the axis `A`.
+    pub const fn along<A: StaticAxis>(self) -> AxisRange<A> {
+        AxisRange::from_span(self)
+    }
+}
+
+impl From<Range<i32>> for Span {
+    fn from(Range { start, end }: Range<i32>) -> Self {
+        Self::new(start, end)
+    }
+}
+
+impl From<Span> for Range<i32> {
+    fn from(Span { start, end }: Span) -> Self {
+        start..end
+    }
+}
+
+impl<A: StaticAxis> From<AxisRange<A>> for Span {
+    fn from(range: AxisRange<A>) -> Self {
+        range.span
+    }
+}
+
+impl<A: StaticAxis> From<Span> for AxisRange<A> {
+    fn from(span: Span) -> Self {
+        span.along()
+    }
+}
+
+impl IntoIterator for Span {
+    type Item = i32;
+    type IntoIter = Range<i32>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.start..self.end
+    }
+}
+
+impl Size {
+    /// The positions of the cells of this size along `axis`, starting at 0.
+    pub const fn span(self, axis: Axis) -> Span {
+        Span::of_size(self, axis)
+    }
+
+    /// The positions of the cells of this size along `A`, starting at 0.
+    pub fn span_static<A: StaticAxis>(self) -> Span {
+        Span::of_size_static::<A>(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Span;
+    use crate::coord::{static_axis, Axis, Coord, Size};
+
+    #[test]
+    fn set_operations() {
+        let a = Span::new(2, 8);
+        let b = Span::with_len(5, 5);
+        assert_eq!(b, Span::new(5, 10));
+        assert_eq!(a.intersection(b), Span::new(5, 8));
+        assert!(a.intersection(Span::new(10, 12)).is_empty());
+        assert_eq!(a.union(b), Some(Span::new(2, 10)));
+        assert_eq!(a.union(Span::new(8, 9)), Some(Span::new(2, 9)));
+        assert_eq!(a.union(Span::new(9, 10)), None);
+        assert_eq!(a.hull(Span::new(9, 10)), Span::new(2, 10));
+        assert_eq!(a.union(Span::new(100, 100)), Some(a));
+        assert!(a.contains_span(Span::new(3, 8)));
+        assert!(!a.contains_span(b));
+        assert!(a.contains_span(Span::new(100, 50)));
+        assert_eq!(
+            a.difference(Span::new(4, 6)),
+            (Span::new(2, 4), Span::new(6, 8))
+        );
+        let (before, after) = a.difference(b);
+        assert_eq!(before, Span::new(2, 5));
+        assert!(after.is_empty());
+        let (before, after) = a.difference(Span::new(0, 20));
+        assert!(before.is_empty() && after.is_empty());
+        let (before, after) = a.difference(Span::new(20, 30));
+        assert_eq!(before, a);
+        assert!(after.is_empty());
+        assert_eq!(Span::new(5, 3).len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "width 4294967295 is too large for a size")]
+    fn to_rect_too_long() {
+        Span::new(i32::MIN, i32::MAX).to_rect(Span::new(0, 1), Axis::X);
+    }
+
+    #[test]
+    fn projection() {
+        let size = Size::new(80, 24);
+        assert_eq!(size.span(Axis::X), Span::new(0, 80));
+        assert_eq!(size.span_static::<static_axis::Y>(), Span::new(0, 24));
+        let top_left = Coord::new(3, -2);
+        let along = Span::of_rect(top_left, size, Axis::Y);
+        let across = Span::of_rect_static::<static_axis::X>(top_left, size);
+        assert_eq!(along, Span::new(-2, 22));
+        assert_eq!(along.to_rect(across, Axis::Y), (top_left, size));
+        assert_eq!(
+            across.to_rect_static::<static_axis::X>(along.shift(1)),
+            (Coord::new(3, -1), size)
+        );
+        let full = Span::new(i32::MIN, i32::MAX);
+        assert_eq!(full.checked_to_rect(Span::new(0, 1), Axis::X), None);
+        assert_eq!(
+            Span::new(0, 1).checked_to_rect_static::<static_axis::Y>(full),
+            None
+        );
+        assert_eq!(
+            along.checked_to_rect(across, Axis::Y),
+            Some((top_left, size))
+        );
+        assert_eq!(
+            Span::with_len(i32::MAX - 1, 10),
+            Span::new(i32::MAX - 1, i32::MAX)
+        );
+        assert_eq!(
+            Span::new(1, 4)
+                .along::<static_axis::X>()
+                .line(7)
+                .next_back(),
+            Some(Coord::new(3, 7))
+        );
+    }
+}
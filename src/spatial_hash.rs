@@ -0,0 +1,324 @@
+//! A spatial index of values at points in an unbounded world, for frequently moving entities.
+//!
+//! The world is divided into buckets of a fixed size, numbered like the chunks of a
+//! `ChunkGrid`, and each value is stored in the bucket containing its point. Moving a value
+//! within its bucket only updates its point, and moving it to another bucket costs a
+//! logarithmic number of operations in the number of buckets. Buckets are created when a value
+//! is first inserted into them and removed when they become empty. A query visits only the
+//! buckets in the columns of buckets overlapping the queried area.
+
+use crate::chunk::ChunkGrid;
+use crate::coord::{Coord, Size};
+use crate::metric::Metric;
+use crate::quadtree::Filter;
+use alloc::collections::{btree_map, BTreeMap};
+use alloc::vec::Vec;
+use core::{ops::RangeInclusive, slice};
+
+/// The minimum and maximum corners of the smallest rectangle containing every cell matched by
+/// `filter`, or `None` if nothing matches.
+fn bounding_box(filter: Filter) -> Option<(Coord, Coord)> {
+    let clamp = |value: i64| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+    match filter {
+        Filter::All => Some((
+            Coord::new(i32::MIN, i32::MIN),
+            Coord::new(i32::MAX, i32::MAX),
+        )),
+        Filter::Rect { x0, y0, x1, y1 } => (x0 < x1 && y0 < y1).then(|| {
+            (
+                Coord::new(x0 as i32, y0 as i32),
+                Coord::new(clamp(x1 - 1), clamp(y1 - 1)),
+            )
+        }),
+        Filter::Within { centre, radius, .. } => {
+            // The cells within a radius under any metric are within the square of cells
+            // within that radius under the Chebyshev metric.
+            let radius = radius as i64;
+            let (x, y) = (centre.x as i64, centre.y as i64);
+            Some((
+                Coord::new(clamp(x - radius), clamp(y - radius)),
+                Coord::new(clamp(x + radius), clamp(y + radius)),
+            ))
+        }
+    }
+}
+
+/// A spatial index of values at points, bucketed by a fixed bucket size, supporting cheap
+/// moves and rectangle and radius queries. Any number of values may be at the same point.
+#[derive(Debug, Clone)]
+pub struct SpatialHash<T> {
+    grid: ChunkGrid,
+    /// Keyed by bucket coordinate, ordered by column then row.
+    buckets: BTreeMap<Coord, Vec<(Coord, T)>>,
+    len: usize,
+}
+
+impl<T> SpatialHash<T> {
+    /// Panics if `bucket_size` is empty.
+    pub const fn new(bucket_size: Size) -> Self {
+        Self {
+            grid: ChunkGrid::new(bucket_size),
+            buckets: BTreeMap::new(),
+            len: 0,
+        }
+    }
+
+    pub const fn bucket_size(&self) -> Size {
+        self.grid.chunk_size()
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.len = 0;
+    }
+
+    fn bucket_of(&self, coord: Coord) -> Coord {
+        self.grid.split(coord).chunk
+    }
+
+    pub fn insert(&mut self, coord: Coord, value: T) {
+        let bucket = self.bucket_of(coord);
+        match self.buckets.get_mut(&bucket) {
+            Some(entries) => entries.push((coord, value)),
+            None => {
+                self.buckets.insert(bucket, alloc::vec![(coord, value)]);
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Removes and returns a value at `coord` equal to `value`.
+    pub fn remove(&mut self, coord: Coord, value: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        self.remove_where(coord, |entry| entry == value)
+    }
+
+    /// Removes and returns a value at `coord` for which `f` returns true.
+    pub fn remove_where<F: FnMut(&T) -> bool>(&mut self, coord: Coord, mut f: F) -> Option<T> {
+        let bucket = self.bucket_of(coord);
+        let entries = self.buckets.get_mut(&bucket)?;
+        let index = entries
+            .iter()
+            .position(|(entry_coord, value)| *entry_coord == coord && f(value))?;
+        let (_, removed) = entries.swap_remove(index);
+        if entries.is_empty() {
+            self.buckets.remove(&bucket);
+        }
+        self.len -= 1;
+        Some(removed)
+    }
+
+    /// Moves a value at `from` equal to `value` to `to`, returning false if there's no such
+    /// value.
+    pub fn move_value(&mut self, from: Coord, to: Coord, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.move_where(from, to, |entry| entry == value)
+    }
+
+    /// Moves a value at `from` for which `f` returns true to `to`, returning false if there's
+    /// no such value. Moving a value within its bucket doesn't change the set of buckets.
+    pub fn move_where<F: FnMut(&T) -> bool>(&mut self, from: Coord, to: Coord, mut f: F) -> bool {
+        let bucket = self.bucket_of(from);
+        if bucket == self.bucket_of(to) {
+            let entry = self.buckets.get_mut(&bucket).and_then(|entries| {
+                entries
+                    .iter_mut()
+                    .find(|(entry_coord, value)| *entry_coord == from && f(value))
+            });
+            match entry {
+                Some((coord, _)) => {
+                    *coord = to;
+                    true
+                }
+                None => false,
+            }
+        } else {
+            match self.remove_where(from, f) {
+                Some(value) => {
+                    self.insert(to, value);
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    /// The entries in the bucket containing `coord`, in no particular order.
+    pub fn bucket(&self, coord: Coord) -> &[(Coord, T)] {
+        self.buckets
+            .get(&self.bucket_of(coord))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn query(&self, filter: Filter) -> Query<'_, T> {
+        // Buckets are ordered by column, so the range covers every column from `min.x` to
+        // `max.x`, and buckets in rows outside `rows` are skipped while iterating.
+        let (buckets, rows) = match bounding_box(filter) {
+            Some((min, max)) => {
+                let (min, max) = (self.bucket_of(min), self.bucket_of(max));
+                (self.buckets.range(min..=max), min.y..=max.y)
+            }
+            None => (
+                self.buckets.range(Coord::new(0, 0)..Coord::new(0, 0)),
+                0..=0,
+            ),
+        };
+        Query {
+            filter,
+            buckets,
+            rows,
+            entries: [].iter(),
+        }
+    }
+
+    /// Iterates over every entry, in no particular order.
+    pub fn iter(&self) -> Query<'_, T> {
+        self.query(Filter::All)
+    }
+
+    /// Iterates over the entries within the rectangle with top-left corner `top_left` and
+    /// dimensions `size`, in no particular order.
+    pub fn rect(&self, top_left: Coord, size: Size) -> Query<'_, T> {
+        self.query(Filter::rect(top_left, size))
+    }
+
+    /// Iterates over the entries at most `radius` from `centre` under `metric`, in no
+    /// particular order.
+    pub fn within(&self, centre: Coord, radius: u32, metric: Metric) -> Query<'_, T> {
+        self.query(Filter::within(centre, radius, metric))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SpatialHash<T> {
+    type Item = (Coord, &'a T);
+    type IntoIter = Query<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a `SpatialHash` matching a query.
+#[derive(Debug, Clone)]
+pub struct Query<'a, T> {
+    filter: Filter,
+    buckets: btree_map::Range<'a, Coord, Vec<(Coord, T)>>,
+    rows: RangeInclusive<i32>,
+    entries: slice::Iter<'a, (Coord, T)>,
+}
+
+impl<'a, T> Iterator for Query<'a, T> {
+    type Item = (Coord, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            for (coord, value) in self.entries.by_ref() {
+                if self.filter.contains(*coord) {
+                    return Some((*coord, value));
+                }
+            }
+            let (bucket, entries) = self.buckets.next()?;
+            if self.rows.contains(&bucket.y) {
+                self.entries = entries.iter();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SpatialHash;
+    use crate::arbitrary_impls::with_proptest::size_up_to;
+    use crate::coord::{Coord, Size};
+    use crate::metric::Metric;
+    use crate::test_strategies::{coord_in, indices_in_rect, indices_within, metric};
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn move_between_buckets() {
+        let mut hash = SpatialHash::new(Size::new(4, 4));
+        hash.insert(Coord::new(1, 1), 'a');
+        hash.insert(Coord::new(-1, -1), 'b');
+        assert_eq!(hash.bucket(Coord::new(3, 3)), [(Coord::new(1, 1), 'a')]);
+        assert!(hash.move_value(Coord::new(1, 1), Coord::new(2, 3), &'a'));
+        assert_eq!(hash.buckets.len(), 2);
+        assert!(hash.move_value(Coord::new(2, 3), Coord::new(-4, -4), &'a'));
+        assert!(!hash.move_value(Coord::new(2, 3), Coord::new(0, 0), &'a'));
+        assert_eq!(hash.buckets.len(), 1);
+        assert_eq!(hash.len(), 2);
+        assert_eq!(
+            hash.within(Coord::new(-3, -3), 1, Metric::Chebyshev)
+                .collect::<Vec<_>>(),
+            [(Coord::new(-4, -4), &'a')]
+        );
+        assert_eq!(hash.remove(Coord::new(-1, -1), &'b'), Some('b'));
+        assert_eq!(hash.remove(Coord::new(-1, -1), &'b'), None);
+        assert_eq!(hash.rect(Coord::new(-4, -4), Size::new(0, 5)).count(), 0);
+        assert_eq!(hash.iter().count(), 1);
+    }
+
+    #[test]
+    fn extreme_coordinates() {
+        let mut hash = SpatialHash::new(Size::new(1, 1));
+        hash.insert(Coord::new(i32::MIN, i32::MAX), ());
+        hash.insert(Coord::new(i32::MAX, i32::MIN), ());
+        assert_eq!(hash.iter().count(), 2);
+        assert_eq!(
+            hash.within(Coord::new(i32::MAX, i32::MIN), u32::MAX, Metric::Manhattan)
+                .count(),
+            1
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn moves_keep_buckets_consistent(
+            entries in proptest::collection::vec(coord_in(-30..30), 0..100),
+            moves in proptest::collection::vec((any::<prop::sample::Index>(), coord_in(-30..30)), 0..50),
+            bucket_size in size_up_to(Size::new(7, 7)).prop_filter("non-empty", |size| !size.is_empty()),
+            (top_left, w, h) in (coord_in(-40..40), 0..30u32, 0..30u32),
+            radius in 0..20u32,
+            metric in metric(),
+        ) {
+            let mut hash = SpatialHash::new(bucket_size);
+            let mut coords = entries;
+            for (i, &coord) in coords.iter().enumerate() {
+                hash.insert(coord, i);
+            }
+            if !coords.is_empty() {
+                for (index, to) in moves {
+                    let i = index.index(coords.len());
+                    prop_assert!(hash.move_value(coords[i], to, &i));
+                    coords[i] = to;
+                }
+            }
+            // Every value is in the bucket containing its point, and emptied buckets are gone.
+            for (&bucket, entries) in &hash.buckets {
+                prop_assert!(!entries.is_empty());
+                for &(coord, i) in entries {
+                    prop_assert_eq!(hash.bucket_of(coord), bucket);
+                    prop_assert_eq!(coords[i], coord);
+                }
+            }
+            let occupied = coords.iter().map(|&coord| hash.bucket_of(coord)).collect::<BTreeSet<_>>();
+            prop_assert_eq!(hash.buckets.len(), occupied.len());
+            let mut rect = hash.rect(top_left, Size::new(w, h)).map(|(_, &i)| i).collect::<Vec<_>>();
+            rect.sort();
+            prop_assert_eq!(rect, indices_in_rect(&coords, top_left, Size::new(w, h)));
+            let mut within = hash.within(top_left, radius, metric).map(|(_, &i)| i).collect::<Vec<_>>();
+            within.sort();
+            prop_assert_eq!(within, indices_within(&coords, top_left, radius, metric));
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Proptest strategies, and brute-force results to check queries against, shared by the tests
+//! of several modules.
+
+use crate::coord::Coord;
+#[cfg(feature = "alloc")]
+use crate::coord::Size;
+#[cfg(feature = "alloc")]
+use crate::metric::Metric;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ops::Range;
+use proptest::prelude::*;
+
+/// Coordinates whose components are both in `range`.
+pub fn coord_in(range: Range<i32>) -> impl Strategy<Value = Coord> {
+    (range.clone(), range).prop_map(Coord::from)
+}
+
+#[cfg(feature = "alloc")]
+pub fn metric() -> impl Strategy<Value = Metric> {
+    prop_oneof![
+        Just(Metric::Manhattan),
+        Just(Metric::Chebyshev),
+        Just(Metric::Euclidean),
+    ]
+}
+
+/// The indices of the coordinates in `coords` within the rectangle with top-left corner
+/// `top_left` and dimensions `size`, in increasing order.
+#[cfg(feature = "alloc")]
+pub fn indices_in_rect(coords: &[Coord], top_left: Coord, size: Size) -> Vec<usize> {
+    let (x0, y0) = (top_left.x as i64, top_left.y as i64);
+    let (x1, y1) = (x0 + size.width() as i64, y0 + size.height() as i64);
+    (0..coords.len())
+        .filter(|&i| {
+            let (x, y) = (coords[i].x as i64, coords[i].y as i64);
+            x >= x0 && x < x1 && y >= y0 && y < y1
+        })
+        .collect()
+}
+
+/// The indices of the coordinates in `coords` at most `radius` from `centre` under `metric`,
+/// in increasing order.
+#[cfg(feature = "alloc")]
+pub fn indices_within(coords: &[Coord], centre: Coord, radius: u32, metric: Metric) -> Vec<usize> {
+    (0..coords.len())
+        .filter(|&i| metric.is_within(centre, coords[i], radius))
+        .collect()
+}
@@ -0,0 +1,345 @@
+//! Stable text format for `Coord`, `Size`, `Axis` and `Rule`.
+//!
+//! The grammar accepted by the `FromStr` implementations is:
+//!
+//! ```text
+//! coord := "(" int "," int ")"
+//! size  := uint "x" uint
+//! axis  := "x" | "y" | "X" | "Y"
+//! rule  := ("B" | "b") count* "/" ("S" | "s") count*
+//! count := "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8"
+//! ```
+//!
+//! Whitespace is allowed around every token except within a run of counts. `int` and `uint`
+//! are decimal integers as accepted by `i32::from_str` and `u32::from_str` respectively. The
+//! `Display` implementations produce the canonical form with no whitespace, e.g. `(3,-4)`,
+//! `80x24`, `x` and `B3/S23`, which always parses back to the same value.
+
+use crate::coord::{Axis, Coord, Size};
+use crate::error::{ParseError, ParseTarget};
+use crate::rule::{Rule, MAX_NEIGHBOURS};
+use core::{fmt, str::FromStr};
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.width(), self.height())
+    }
+}
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Axis::X => f.write_str("x"),
+            Axis::Y => f.write_str("y"),
+        }
+    }
+}
+
+/// Writes the counts in ascending order.
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("B")?;
+        for count in (0..=MAX_NEIGHBOURS).filter(|&count| self.is_birth(count)) {
+            write!(f, "{}", count)?;
+        }
+        f.write_str("/S")?;
+        for count in (0..=MAX_NEIGHBOURS).filter(|&count| self.is_survival(count)) {
+            write!(f, "{}", count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Tracks the position within the input so that errors can report where they occurred.
+struct Cursor<'a> {
+    input: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            rest: input.trim_start(),
+        }
+    }
+
+    fn position(&self) -> usize {
+        self.input.len() - self.rest.len()
+    }
+
+    fn syntax_error(&self, expected: &'static str) -> ParseError {
+        ParseError::Syntax {
+            expected,
+            position: self.position(),
+        }
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), ParseError> {
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest.trim_start();
+                Ok(())
+            }
+            None => Err(self.syntax_error(expected)),
+        }
+    }
+
+    /// Consumes everything up to the next occurrence of `terminator` (or the end of input if
+    /// `terminator` is `None`) and parses it as the integer along `axis` of a `target`.
+    // `IntErrorKind` is only `Copy` on compilers newer than the minimum supported version.
+    #[allow(clippy::clone_on_copy)]
+    fn integer<T: FromStr<Err = core::num::ParseIntError>>(
+        &mut self,
+        terminator: Option<char>,
+        target: ParseTarget,
+        axis: Axis,
+    ) -> Result<T, ParseError> {
+        let end = match terminator {
+            Some(terminator) => self.rest.find(terminator).unwrap_or(self.rest.len()),
+            None => self.rest.len(),
+        };
+        let (token, rest) = self.rest.split_at(end);
+        let value = token
+            .trim_end()
+            .parse::<T>()
+            .map_err(|e| ParseError::InvalidInteger {
+                target,
+                axis,
+                kind: e.kind().clone(),
+            })?;
+        self.rest = rest;
+        Ok(value)
+    }
+
+    /// Consumes a run of neighbour counts from 0 to 8, adding them to `rule`.
+    fn counts(&mut self, rule: &mut Rule, birth: bool) {
+        while let Some(count) = self
+            .rest
+            .bytes()
+            .next()
+            .filter(|b| (b'0'..=b'8').contains(b))
+        {
+            rule.set(birth, count - b'0');
+            self.rest = &self.rest[1..];
+        }
+        self.rest = self.rest.trim_start();
+    }
+
+    fn end(&self) -> Result<(), ParseError> {
+        if self.rest.trim_end().is_empty() {
+            Ok(())
+        } else {
+            Err(self.syntax_error("end of input"))
+        }
+    }
+}
+
+/// Parses the format `(x,y)`. See the module documentation for the full grammar.
+impl FromStr for Coord {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        cursor.expect('(', "'('")?;
+        let x = cursor.integer(Some(','), ParseTarget::Coord, Axis::X)?;
+        cursor.expect(',', "','")?;
+        let y = cursor.integer(Some(')'), ParseTarget::Coord, Axis::Y)?;
+        cursor.expect(')', "')'")?;
+        cursor.end()?;
+        Ok(Coord::new(x, y))
+    }
+}
+
+/// Parses the format `WIDTHxHEIGHT`. See the module documentation for the full grammar.
+impl FromStr for Size {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        let width = cursor.integer(Some('x'), ParseTarget::Size, Axis::X)?;
+        cursor.expect('x', "'x'")?;
+        let height = cursor.integer(None, ParseTarget::Size, Axis::Y)?;
+        cursor.end()?;
+        Ok(Size::try_new(width, height)?)
+    }
+}
+
+/// Parses `x` or `y`, in either case.
+impl FromStr for Axis {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        let axis = match cursor.rest.chars().next() {
+            Some('x' | 'X') => Axis::X,
+            Some('y' | 'Y') => Axis::Y,
+            _ => return Err(cursor.syntax_error("'x' or 'y'")),
+        };
+        cursor.rest = &cursor.rest[1..];
+        cursor.end()?;
+        Ok(axis)
+    }
+}
+
+/// Parses the format `B3/S23`. See the module documentation for the full grammar.
+impl FromStr for Rule {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        let mut rule = Rule::default();
+        if !cursor.rest.starts_with(['B', 'b']) {
+            return Err(cursor.syntax_error("'B'"));
+        }
+        cursor.rest = &cursor.rest[1..];
+        cursor.counts(&mut rule, true);
+        cursor.expect('/', "a neighbour count from 0 to 8 or '/'")?;
+        if !cursor.rest.starts_with(['S', 's']) {
+            return Err(cursor.syntax_error("'S'"));
+        }
+        cursor.rest = &cursor.rest[1..];
+        cursor.counts(&mut rule, false);
+        if !cursor.rest.trim_end().is_empty() {
+            return Err(cursor.syntax_error("a neighbour count from 0 to 8 or end of input"));
+        }
+        Ok(rule)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::coord::{Axis, Coord, Size};
+    use crate::error::{DimensionTooLargeForSize, ParseError, ParseTarget};
+    use crate::rule::Rule;
+    use core::num::IntErrorKind;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("(3,-4)".parse(), Ok(Coord::new(3, -4)));
+        assert_eq!(" ( 3 , -4 ) ".parse(), Ok(Coord::new(3, -4)));
+        assert_eq!("80x24".parse(), Ok(Size::new(80, 24)));
+        assert_eq!(" 80 x 24 ".parse(), Ok(Size::new(80, 24)));
+        assert_eq!("Y".parse(), Ok(Axis::Y));
+        assert_eq!("B3/S23".parse(), Ok(Rule::LIFE));
+        assert_eq!(" b3 / s32 ".parse(), Ok(Rule::LIFE));
+        assert_eq!("B/S".parse(), Ok(Rule::default()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            "(3 -4)".parse::<Coord>(),
+            Err(ParseError::InvalidInteger {
+                target: ParseTarget::Coord,
+                axis: Axis::X,
+                kind: IntErrorKind::InvalidDigit,
+            })
+        );
+        assert_eq!(
+            "(3,4".parse::<Coord>(),
+            Err(ParseError::Syntax {
+                expected: "')'",
+                position: 4,
+            })
+        );
+        assert_eq!(
+            "(3,4) 5".parse::<Coord>(),
+            Err(ParseError::Syntax {
+                expected: "end of input",
+                position: 6,
+            })
+        );
+        assert_eq!(
+            "80x".parse::<Size>(),
+            Err(ParseError::InvalidInteger {
+                target: ParseTarget::Size,
+                axis: Axis::Y,
+                kind: IntErrorKind::Empty,
+            })
+        );
+        assert_eq!(
+            "80x-1".parse::<Size>(),
+            Err(ParseError::InvalidInteger {
+                target: ParseTarget::Size,
+                axis: Axis::Y,
+                kind: IntErrorKind::InvalidDigit,
+            })
+        );
+        assert_eq!(
+            "4000000000x1".parse::<Size>(),
+            Err(ParseError::DimensionTooLargeForSize(
+                DimensionTooLargeForSize {
+                    axis: Axis::X,
+                    value: 4000000000,
+                }
+            ))
+        );
+        assert_eq!(
+            "z".parse::<Axis>(),
+            Err(ParseError::Syntax {
+                expected: "'x' or 'y'",
+                position: 0,
+            })
+        );
+        assert_eq!(
+            "B39/S23".parse::<Rule>(),
+            Err(ParseError::Syntax {
+                expected: "a neighbour count from 0 to 8 or '/'",
+                position: 2,
+            })
+        );
+        assert_eq!(
+            "S23/B3".parse::<Rule>(),
+            Err(ParseError::Syntax {
+                expected: "'B'",
+                position: 0,
+            })
+        );
+        assert_eq!(
+            "B3/S2 3".parse::<Rule>(),
+            Err(ParseError::Syntax {
+                expected: "a neighbour count from 0 to 8 or end of input",
+                position: 6,
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn error_messages() {
+        let e = "99999999999x1".parse::<Size>().unwrap_err();
+        assert_eq!(e.to_string(), "width is too large");
+        let e = "(1,99999999999)".parse::<Coord>().unwrap_err();
+        assert_eq!(e.to_string(), "y component is too large");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn round_trip() {
+        for coord in [Coord::new(0, 0), Coord::new(i32::MIN, i32::MAX)] {
+            assert_eq!(coord.to_string().parse(), Ok(coord));
+        }
+        for size in [Size::new(0, 0), Size::new(crate::MAX_SIZE_FIELD, 1)] {
+            assert_eq!(size.to_string().parse(), Ok(size));
+        }
+        assert_eq!(Coord::new(3, -4).to_string(), "(3,-4)");
+        assert_eq!(Size::new(80, 24).to_string(), "80x24");
+        assert_eq!(Axis::X.to_string(), "x");
+        for rule in [Rule::LIFE, Rule::CAVE, Rule::default()] {
+            assert_eq!(rule.to_string().parse(), Ok(rule));
+        }
+        assert_eq!(Rule::CAVE.to_string(), "B678/S345678");
+    }
+
+    proptest! {
+        #[test]
+        fn parse_display_round_trip(coord in any::<Coord>(), size in any::<Size>()) {
+            prop_assert_eq!(coord.to_string().parse::<Coord>(), Ok(coord));
+            prop_assert_eq!(size.to_string().parse::<Size>(), Ok(size));
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Mapping between world coordinates and the screen coordinates of a rectangular window onto
+//! the world.
+
+use crate::coord::{Coord, CoordIterRowMajor, Size};
+use crate::fit::Align;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// A window of size `size` onto the world, whose top-left cell shows the world coordinate
+/// `top_left`. Screen coordinates are relative to the top-left of the window.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub struct Viewport {
+    pub top_left: Coord,
+    pub size: Size,
+}
+
+/// A cell visible in a `Viewport`, as its coordinates on the screen and in the world.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ViewportCell {
+    pub screen: Coord,
+    pub world: Coord,
+}
+
+/// Clamps the start of a window of length `view` so it stays within `0..world`, or centres
+/// the world in the window if the window is longer.
+const fn clamp_start(start: i32, view: u32, world: u32) -> i32 {
+    if view >= world {
+        return Align::Centre.offset(view, world);
+    }
+    // `world - view` can be `MAX_SIZE_FIELD`, which doesn't fit in an `i32`, but no valid
+    // start is greater than `i32::MAX`.
+    let max = (world - view) as i64;
+    let max = if max > i32::MAX as i64 {
+        i32::MAX
+    } else {
+        max as i32
+    };
+    if start < 0 {
+        0
+    } else if start > max {
+        max
+    } else {
+        start
+    }
+}
+
+impl Viewport {
+    pub const fn new(top_left: Coord, size: Size) -> Self {
+        Self { top_left, size }
+    }
+
+    /// A viewport of the given size whose centre shows `target`. If the size is even along
+    /// an axis, the target is just after the middle.
+    pub const fn centred_on(target: Coord, size: Size) -> Self {
+        Self::new(target.const_sub_size(size.const_div(2)), size)
+    }
+
+    /// Moves the viewport so its centre shows `target`.
+    #[must_use]
+    pub const fn centre_on(self, target: Coord) -> Self {
+        Self::centred_on(target, self.size)
+    }
+
+    /// Moves the viewport by `delta` in world coordinates.
+    #[must_use]
+    pub const fn scroll(self, delta: Coord) -> Self {
+        Self::new(self.top_left.const_add(delta), self.size)
+    }
+
+    /// Moves the viewport the smallest distance which keeps it within a world of size
+    /// `world`, whose top-left is at the origin. Along an axis where the viewport is larger
+    /// than the world it's moved so the world is centred. This is the viewport equivalent of
+    /// `Coord::constrain`.
+    #[must_use]
+    pub const fn clamp_within(self, world: Size) -> Self {
+        Self::new(
+            Coord::new(
+                clamp_start(self.top_left.x, self.size.width(), world.width()),
+                clamp_start(self.top_left.y, self.size.height(), world.height()),
+            ),
+            self.size,
+        )
+    }
+
+    /// The screen coordinate at which `world` appears. The result is outside the viewport if
+    /// `world` isn't visible.
+    pub const fn to_screen(self, world: Coord) -> Coord {
+        world.const_sub(self.top_left)
+    }
+
+    /// The screen coordinate at which `world` appears, or `None` if it isn't visible.
+    pub const fn to_screen_visible(self, world: Coord) -> Option<Coord> {
+        let screen = self.to_screen(world);
+        if screen.is_valid(self.size) {
+            Some(screen)
+        } else {
+            None
+        }
+    }
+
+    /// The world coordinate shown at `screen`.
+    pub const fn to_world(self, screen: Coord) -> Coord {
+        self.top_left.const_add(screen)
+    }
+
+    pub const fn is_visible(self, world: Coord) -> bool {
+        self.to_screen(world).is_valid(self.size)
+    }
+
+    /// Iterates over every cell of the viewport in row-major order.
+    pub const fn visible_cells(self) -> VisibleCells {
+        VisibleCells {
+            top_left: self.top_left,
+            screen: self.size.coord_iter_row_major(),
+        }
+    }
+}
+
+/// Iterator over the cells of a `Viewport`, returned by `Viewport::visible_cells`.
+#[derive(Debug, Clone)]
+pub struct VisibleCells {
+    top_left: Coord,
+    screen: CoordIterRowMajor,
+}
+
+impl Iterator for VisibleCells {
+    type Item = ViewportCell;
+    fn next(&mut self) -> Option<Self::Item> {
+        let screen = self.screen.next()?;
+        Some(ViewportCell {
+            screen,
+            world: self.top_left + screen,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Viewport, ViewportCell};
+    use crate::coord::{Coord, Size, MAX_SIZE_FIELD};
+
+    #[test]
+    fn mapping() {
+        let viewport = Viewport::centred_on(Coord::new(10, 10), Size::new(5, 4));
+        assert_eq!(viewport.top_left, Coord::new(8, 8));
+        assert_eq!(viewport.to_screen(Coord::new(10, 10)), Coord::new(2, 2));
+        assert_eq!(viewport.to_world(Coord::new(2, 2)), Coord::new(10, 10));
+        assert_eq!(viewport.to_screen_visible(Coord::new(13, 8)), None);
+        assert_eq!(
+            viewport.to_screen_visible(Coord::new(12, 11)),
+            Some(Coord::new(4, 3))
+        );
+        assert!(!viewport.is_visible(Coord::new(7, 9)));
+        assert_eq!(
+            viewport.scroll(Coord::new(-1, 2)).top_left,
+            Coord::new(7, 10)
+        );
+    }
+
+    #[test]
+    fn clamp() {
+        let world = Size::new(20, 3);
+        let viewport = Viewport::new(Coord::new(-4, 1), Size::new(6, 6));
+        let clamped = viewport.clamp_within(world);
+        assert_eq!(clamped.top_left, Coord::new(0, -2));
+        assert_eq!(
+            viewport
+                .centre_on(Coord::new(100, 0))
+                .clamp_within(world)
+                .top_left,
+            Coord::new(14, -2)
+        );
+        let inside = Viewport::new(Coord::new(3, 0), Size::new(4, 3));
+        assert_eq!(inside.clamp_within(world), inside);
+    }
+
+    #[test]
+    fn clamp_within_max_world() {
+        let world = Size::new(MAX_SIZE_FIELD, 1);
+        let viewport = Viewport::new(Coord::new(5, 0), Size::new(0, 1));
+        assert_eq!(viewport.clamp_within(world), viewport);
+        let far = Viewport::new(Coord::new(i32::MAX, 0), Size::new(0, 1));
+        assert_eq!(far.clamp_within(world), far);
+    }
+
+    #[test]
+    fn visible_cells() {
+        let viewport = Viewport::new(Coord::new(-1, 5), Size::new(2, 2));
+        let cells = viewport.visible_cells().collect::<Vec<_>>();
+        assert_eq!(cells.len(), 4);
+        assert_eq!(
+            cells[1],
+            ViewportCell {
+                screen: Coord::new(1, 0),
+                world: Coord::new(0, 5),
+            }
+        );
+        assert!(cells.iter().all(|c| viewport.to_world(c.screen) == c.world));
+    }
+}